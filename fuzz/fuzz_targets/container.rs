@@ -0,0 +1,28 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Soter container parsing.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use soter::container::Container;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(container) = Container::parse(data) {
+        // Container encoding is canonical, valid ones must survive a round trip.
+        let encoded = container.to_vec().expect("valid container can be encoded");
+        assert_eq!(encoded, data);
+    }
+});
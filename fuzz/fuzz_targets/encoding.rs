@@ -0,0 +1,33 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Base64 and hex decoding.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use soter::encoding;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(decoded) = encoding::base64_decode(data) {
+        // Decoding is strict, only padding may be omitted.
+        let encoded = encoding::base64_encode(&decoded);
+        let unpadded = encoded.trim_end_matches('=');
+        assert!(data == encoded.as_bytes() || data == unpadded.as_bytes());
+    }
+    if let Ok(decoded) = encoding::hex_decode(data) {
+        let encoded = encoding::hex_encode(&decoded);
+        assert!(encoded.as_bytes().eq_ignore_ascii_case(data));
+    }
+});
@@ -0,0 +1,64 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Themis message framing.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use themis::framing::{read_frame, FrameDecoder};
+
+const MAX_FRAME_SIZE: usize = 64 * 1024;
+
+fuzz_target!(|data: &[u8]| {
+    // Feed the data byte by byte to exercise incremental parsing,
+    // and make sure it agrees with the blocking reader.
+    let mut decoder = FrameDecoder::new(MAX_FRAME_SIZE);
+    let mut decoded = Vec::new();
+    let mut failed = false;
+    for byte in data {
+        decoder.push(std::slice::from_ref(byte));
+        match decoder.next_frame() {
+            Ok(Some(frame)) => decoded.push(frame),
+            Ok(None) => {}
+            Err(_) => {
+                failed = true;
+                break;
+            }
+        }
+    }
+
+    let mut reader = data;
+    let mut read = Vec::new();
+    let mut read_failed = false;
+    loop {
+        match read_frame(&mut reader, MAX_FRAME_SIZE) {
+            Ok(Some(frame)) => read.push(frame),
+            Ok(None) => break,
+            Err(_) => {
+                read_failed = true;
+                break;
+            }
+        }
+    }
+
+    assert_eq!(decoded, read);
+    if read_failed {
+        // The reader also fails on truncated frames, the decoder just waits for more data.
+        assert!(failed || decoder.buffered_len() > 0);
+    } else {
+        assert!(!failed);
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+});
@@ -0,0 +1,41 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Themis key parsing.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use themis::keys::{
+    get_key_kind, EcdsaPrivateKey, EcdsaPublicKey, KeyKind, RsaPrivateKey, RsaPublicKey,
+};
+use themis::shamir::KeyShare;
+
+fuzz_target!(|data: &[u8]| {
+    let kind = get_key_kind(data).ok();
+
+    // Exactly one key type accepts a valid key, the one reported by get_key_kind().
+    let rsa_private = RsaPrivateKey::try_from_slice(data).is_ok();
+    let rsa_public = RsaPublicKey::try_from_slice(data).is_ok();
+    let ecdsa_private = EcdsaPrivateKey::try_from_slice(data).is_ok();
+    let ecdsa_public = EcdsaPublicKey::try_from_slice(data).is_ok();
+    assert_eq!(kind == Some(KeyKind::RsaPrivate), rsa_private);
+    assert_eq!(kind == Some(KeyKind::RsaPublic), rsa_public);
+    assert_eq!(kind == Some(KeyKind::EcdsaPrivate), ecdsa_private);
+    assert_eq!(kind == Some(KeyKind::EcdsaPublic), ecdsa_public);
+
+    if let Ok(share) = KeyShare::try_from_slice(data) {
+        assert_eq!(share.as_bytes(), data);
+    }
+});
@@ -0,0 +1,44 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Themis key parsing from Base64.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use themis::keys::{EcdsaPrivateKey, EcdsaPublicKey, RsaPrivateKey, RsaPublicKey, SymmetricKey};
+
+fuzz_target!(|data: &[u8]| {
+    // Keys must survive a round trip through Base64.
+    if let Ok(key) = RsaPrivateKey::from_base64(data) {
+        let decoded = RsaPrivateKey::from_base64(key.to_base64()).unwrap();
+        assert_eq!(decoded.as_bytes(), key.as_bytes());
+    }
+    if let Ok(key) = RsaPublicKey::from_base64(data) {
+        let decoded = RsaPublicKey::from_base64(key.to_base64()).unwrap();
+        assert_eq!(decoded.as_bytes(), key.as_bytes());
+    }
+    if let Ok(key) = EcdsaPrivateKey::from_base64(data) {
+        let decoded = EcdsaPrivateKey::from_base64(key.to_base64()).unwrap();
+        assert_eq!(decoded.as_bytes(), key.as_bytes());
+    }
+    if let Ok(key) = EcdsaPublicKey::from_base64(data) {
+        let decoded = EcdsaPublicKey::from_base64(key.to_base64()).unwrap();
+        assert_eq!(decoded.as_bytes(), key.as_bytes());
+    }
+    if let Ok(key) = SymmetricKey::from_base64(data) {
+        let decoded = SymmetricKey::from_base64(key.to_base64()).unwrap();
+        assert_eq!(decoded.as_bytes(), key.as_bytes());
+    }
+});
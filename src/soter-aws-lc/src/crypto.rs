@@ -0,0 +1,30 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::CStr;
+
+/// Name of the library.
+pub const BACKEND_NAME: &str = "AWS-LC";
+
+/// Returns version of the library, if known.
+pub fn backend_version() -> Option<&'static str> {
+    CStr::from_bytes_with_nul(aws_lc::AWSLC_VERSION_NUMBER_STRING)
+        .ok()
+        .and_then(|version| version.to_str().ok())
+}
+
+/// Returns `true` if the library is running in FIPS mode.
+pub fn FIPS_mode() -> bool {
+    unsafe { aws_lc::FIPS_mode() == 1 }
+}
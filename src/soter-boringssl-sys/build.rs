@@ -5,18 +5,74 @@
 // https://opensource.org/licenses/MIT.
 
 //! Build BoringSSL static library with properly renamed symbols.
+//!
+//! Alternatively, if SOTER_BORINGSSL_DIR environment variable is set, use BoringSSL
+//! which is already built there, renaming its symbols with objcopy.
+//!
+//! If SOTER_BORINGSSL_SRC environment variable is set, build BoringSSL from the source
+//! checkout there instead of the vendored one.
+//!
+//! If SOTER_BORINGSSL_CACHE_DIR environment variable is set, built libraries are stored
+//! there and reused by later builds with the same BoringSSL version and configuration.
+//!
+//! If SOTER_BORINGSSL_PREBUILT_URL environment variable is set, download prebuilt library
+//! from there instead of building it, verifying its SHA-256 checksum.
+//!
+//! Go is needed only to generate err_data.c. If Go is not available, pre-generated file
+//! is used instead: either from SOTER_BORINGSSL_ERR_DATA, or the snapshot in this crate.
 
+use std::collections::BTreeSet;
 use std::env;
 use std::fs;
+use std::path::Path;
 use std::process::{Command, Stdio};
 
 // Relative to CARGO_MANIFEST_DIR
 const BORINGSSL_SRC: &str = "boringssl";
+const ERR_DATA_SNAPSHOT: &str = "generated/err_data.c";
+const PREBUILT_CHECKSUMS: &str = "prebuilt.sha256";
+const WHITELIST: &str = "whitelist.txt";
 
 // Relative to OUT_DIR
 const BUILD_DIR_1: &str = "boringssl/build_1";
 const BUILD_DIR_2: &str = "boringssl/build_2";
 const SYMBOL_FILE: &str = "boringssl/symbols.txt";
+const GO_SHIM_DIR: &str = "boringssl/go";
+const SYSTEM_BUILD_DIR: &str = "boringssl/system";
+const SYSTEM_SYMBOL_MAP: &str = "boringssl/system/symbols.map";
+const PREBUILT_DIR: &str = "boringssl/prebuilt";
+const INCLUDE_DIR: &str = "include";
+
+// Header with prefixed symbol names, included by BoringSSL headers when BORINGSSL_PREFIX is set.
+const PREFIX_HEADER: &str = "boringssl_prefix_symbols.h";
+
+// Environment variable with path to an existing BoringSSL build.
+const SYSTEM_BORINGSSL_DIR: &str = "SOTER_BORINGSSL_DIR";
+
+// Environment variable with path to BoringSSL source code checkout.
+const CUSTOM_BORINGSSL_SRC: &str = "SOTER_BORINGSSL_SRC";
+
+// Environment variable with path to a directory for caching builds.
+const BUILD_CACHE_DIR: &str = "SOTER_BORINGSSL_CACHE_DIR";
+
+// Environment variable with path to pre-generated err_data.c.
+const ERR_DATA_FILE: &str = "SOTER_BORINGSSL_ERR_DATA";
+
+// Environment variable with path to custom CMake toolchain file.
+const CMAKE_TOOLCHAIN_FILE: &str = "SOTER_CMAKE_TOOLCHAIN_FILE";
+
+// Environment variable which disables assembly, same as "no-asm" feature.
+const NO_ASM: &str = "SOTER_BORINGSSL_NO_ASM";
+
+// Environment variable with the number of parallel build jobs.
+const BUILD_JOBS: &str = "SOTER_BUILD_JOBS";
+
+// Environment variables with base URL of prebuilt libraries and expected checksum.
+const PREBUILT_URL: &str = "SOTER_BORINGSSL_PREBUILT_URL";
+const PREBUILT_SHA256: &str = "SOTER_BORINGSSL_PREBUILT_SHA256";
+
+// Environment variable which disables downloads.
+const OFFLINE: &str = "SOTER_BORINGSSL_OFFLINE";
 
 fn env(name: &str) -> String {
     let var = env::var(name).expect(&format!("missing required environment variable {}", name));
@@ -24,11 +80,71 @@ fn env(name: &str) -> String {
     var
 }
 
+fn optional_env(name: &str) -> Option<String> {
+    println!("cargo:rerun-if-env-changed={}", name);
+    env::var(name).ok()
+}
+
 fn main() {
-    validate_dependencies();
+    // BoringSSL uses RtlGenRandom from advapi32 for random numbers on Windows.
+    if env("CARGO_CFG_TARGET_OS") == "windows" {
+        println!("cargo:rustc-link-lib=advapi32");
+    }
+
+    if let Some(boringssl_dir) = optional_env(SYSTEM_BORINGSSL_DIR) {
+        use_system_boringssl(&boringssl_dir);
+        return;
+    }
 
     let manifest_dir = env("CARGO_MANIFEST_DIR");
-    let abs_boringssl_src = format!("{}/{}", manifest_dir, BORINGSSL_SRC);
+    let custom_boringssl_src = optional_env(CUSTOM_BORINGSSL_SRC);
+    let abs_boringssl_src = match &custom_boringssl_src {
+        Some(src) => {
+            validate_boringssl_source(src);
+            src.clone()
+        }
+        None => format!("{}/{}", manifest_dir, BORINGSSL_SRC),
+    };
+
+    let version_string = version_string();
+    let fips = env::var("CARGO_FEATURE_FIPS").is_ok();
+    let target_flags = target_cmake_flags();
+
+    // If a cached build is available then we don't need to build anything,
+    // and don't need any build tools either.
+    let cache_dir = optional_env(BUILD_CACHE_DIR).map(|cache_dir| {
+        let key = build_cache_key(&abs_boringssl_src, &target_flags, fips);
+        format!("{}/{}", cache_dir, key)
+    });
+    let soter_crypto_lib = lib(&format!("soter_crypto_{}", version_string));
+    if let Some(cache_dir) = &cache_dir {
+        if Path::new(&format!("{}/{}", cache_dir, soter_crypto_lib)).is_file() {
+            let symbols = read_symbol_file(&format!("{}/symbols.txt", cache_dir));
+            export_library(cache_dir, &abs_boringssl_src, symbols);
+            return;
+        }
+    }
+
+    // Prebuilt libraries are built from the vendored BoringSSL.
+    if let Some(base_url) = optional_env(PREBUILT_URL).filter(|_| custom_boringssl_src.is_none()) {
+        if use_prebuilt_boringssl(&base_url, &soter_crypto_lib, fips) {
+            let abs_prebuilt_dir = format!("{}/{}", env("OUT_DIR"), PREBUILT_DIR);
+            let symbols = Some(&nm())
+                .filter(|nm| have(nm, &["--version"]))
+                .map(|_| prefixed_symbols(&format!("{}/{}", abs_prebuilt_dir, soter_crypto_lib)));
+            export_library(&abs_prebuilt_dir, &abs_boringssl_src, symbols);
+            return;
+        }
+    }
+
+    // err_data.c snapshot is generated for the vendored BoringSSL too.
+    let go = have_go();
+    let err_data = optional_env(ERR_DATA_FILE).unwrap_or_else(|| match &custom_boringssl_src {
+        Some(_) => String::new(),
+        None => format!("{}/{}", manifest_dir, ERR_DATA_SNAPSHOT),
+    });
+
+    validate_dependencies(go, fips, &err_data);
 
     let out_dir = env("OUT_DIR");
     let abs_build_dir_1 = format!("{}/{}", out_dir, BUILD_DIR_1);
@@ -38,14 +154,19 @@ fn main() {
     fs::create_dir_all(&abs_build_dir_1).expect("failed to create first build directory");
     fs::create_dir_all(&abs_build_dir_2).expect("failed to create second build directory");
 
-    let major = env("CARGO_PKG_VERSION_MAJOR");
-    let minor = env("CARGO_PKG_VERSION_MINOR");
-    let patch = env("CARGO_PKG_VERSION_PATCH");
-    let version_string = format!("{}_{}_{}", major, minor, patch);
     let prefix = format!("__SOTER_BORINGSSL_{}", version_string);
     let cmake_version_flag = format!("-DBORINGSSL_PREFIX={}", prefix);
     let cmake_symbol_listing = "-DBORINGSSL_PREFIX_SYMBOLS=../symbols.txt";
 
+    // Without Go, make BoringSSL "generate" err_data.c by copying the pre-generated file.
+    let cmake_go_flag = if go {
+        None
+    } else {
+        println!("cargo:rerun-if-changed={}", err_data);
+        let go_shim = write_go_shim(&format!("{}/{}", out_dir, GO_SHIM_DIR), &err_data);
+        Some(format!("-DGO_EXECUTABLE={}", go_shim))
+    };
+
     //
     // We build BoringSSL twice. First we run a build to determine what symbols are available.
     //
@@ -61,23 +182,35 @@ fn main() {
             }
         }
     };
+    let (job_args, job_env) = job_control(&build_with);
+    let mut build_args: Vec<&str> = job_args.iter().map(String::as_str).collect();
+    build_args.push("crypto");
+
     let build = |build_dir, flags: &[&str]| {
         let mut flags = flags.to_vec();
 
+        flags.extend(target_flags.iter().map(String::as_str));
+        flags.extend(cmake_go_flag.as_deref());
+
         // Linux builds generally require -fPIC flag so ask CMake to add it.
         flags.push("-DCMAKE_POSITION_INDEPENDENT_CODE=1");
 
+        // Build FIPS module instead of regular libcrypto. It still ends up in libcrypto.a.
+        if fips {
+            flags.push("-DFIPS=1");
+        }
+
         env::set_current_dir(build_dir).expect("failed to cd to build directory");
 
         match build_with {
             BuildSystem::Ninja => {
                 flags.push("-GNinja");
                 run("cmake", &flags);
-                run("ninja", &["crypto"]);
+                run_with_env("ninja", &build_args, &job_env);
             }
             BuildSystem::Make => {
                 run("cmake", &flags);
-                run("make", &["crypto"]);
+                run_with_env("make", &build_args, &job_env);
             }
         }
     };
@@ -88,18 +221,23 @@ fn main() {
     // again with enabled prefixes.
     //
 
-    env::set_current_dir(&abs_boringssl_src).expect("failed to cd to BoringSSL directory");
+    let crypto_1 = format!("{}/crypto/{}", &abs_build_dir_1, lib("crypto"));
+    if go {
+        env::set_current_dir(&abs_boringssl_src).expect("failed to cd to BoringSSL directory");
 
-    run(
-        "go",
-        &[
-            "run",
-            "util/read_symbols.go",
-            "-out",
-            &abs_symbol_file,
-            &format!("{}/crypto/{}", &abs_build_dir_1, lib("crypto")),
-        ],
-    );
+        run(
+            "go",
+            &[
+                "run",
+                "util/read_symbols.go",
+                "-out",
+                &abs_symbol_file,
+                &crypto_1,
+            ],
+        );
+    } else {
+        write_symbol_file(&abs_symbol_file, &crypto_1);
+    }
 
     build(
         &abs_build_dir_2,
@@ -116,8 +254,7 @@ fn main() {
     //
 
     let crypto = format!("{}/crypto/{}", &abs_build_dir_2, lib("crypto"));
-    let soter_crypto = format!("soter_crypto_{}", version_string);
-    let soter_crypto = format!("{}/crypto/{}", &abs_build_dir_2, lib(&soter_crypto));
+    let soter_crypto = format!("{}/crypto/{}", &abs_build_dir_2, soter_crypto_lib);
 
     if let Err(err) = symlink_from_to(&crypto, &soter_crypto) {
         // If the error is an AlreadyExists error, that just means we've already compiled before.
@@ -126,21 +263,858 @@ fn main() {
         }
     }
 
-    println!("cargo:rustc-link-search=native={}/crypto", abs_build_dir_2);
+    // Bindings are generated for the vendored BoringSSL. Make sure that custom one
+    // has all the functions we need, and that they have been prefixed.
+    if custom_boringssl_src.is_some() {
+        validate_prefixed_symbols(&soter_crypto, &prefix);
+    }
+
+    if let Some(cache_dir) = &cache_dir {
+        store_in_cache(
+            cache_dir,
+            &[
+                (&soter_crypto, &soter_crypto_lib),
+                (&abs_symbol_file, "symbols.txt"),
+            ],
+        );
+    }
+
+    export_library(
+        &format!("{}/crypto", abs_build_dir_2),
+        &abs_boringssl_src,
+        read_symbol_file(&abs_symbol_file),
+    );
+}
+
+/// Tells Cargo where the library is and exports metadata for dependent crates.
+///
+/// With `links = "soter_crypto"` their build scripts get DEP_SOTER_CRYPTO_INCLUDE with
+/// BoringSSL headers, DEP_SOTER_CRYPTO_LIB with the library directory, and
+/// DEP_SOTER_CRYPTO_PREFIX which C code must define as BORINGSSL_PREFIX.
+fn export_library(abs_lib_dir: &str, abs_boringssl_src: &str, symbols: Option<Vec<String>>) {
+    println!("cargo:rustc-link-search=native={}", abs_lib_dir);
+
+    let include_dir = format!("{}/{}", env("OUT_DIR"), INCLUDE_DIR);
+    let headers = format!("{}/include/openssl", abs_boringssl_src);
+    if let Err(err) = copy_dir(&headers, &format!("{}/openssl", include_dir)) {
+        println!("cargo:warning=failed to copy BoringSSL headers: {}", err);
+    }
+    match symbols {
+        Some(symbols) => fs::write(
+            format!("{}/{}", include_dir, PREFIX_HEADER),
+            prefix_symbols_header(&symbols),
+        )
+        .expect("failed to write prefix header"),
+        None => println!(
+            "cargo:warning=BoringSSL symbol list is not available, {} not generated",
+            PREFIX_HEADER
+        ),
+    }
+
+    println!("cargo:include={}", include_dir);
+    println!("cargo:lib={}", abs_lib_dir);
+    println!("cargo:prefix=__SOTER_BORINGSSL_{}", version_string());
+}
+
+fn copy_dir(from: &str, to: &str) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let from = entry.path();
+        let to = Path::new(to).join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&from.to_string_lossy(), &to.to_string_lossy())?;
+        } else {
+            fs::copy(&from, &to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Generates the same header as BoringSSL's "util/make_prefix_headers.go" does.
+fn prefix_symbols_header(symbols: &[String]) -> String {
+    let mut header = String::from(
+        "\
+// BORINGSSL_ADD_PREFIX pastes two identifiers into one. It performs one
+// iteration of macro expansion on its arguments before pasting.
+#define BORINGSSL_ADD_PREFIX(a, b) BORINGSSL_ADD_PREFIX_INNER(a, b)
+#define BORINGSSL_ADD_PREFIX_INNER(a, b) a ## _ ## b
+
+",
+    );
+    for symbol in symbols {
+        header.push_str(&format!(
+            "#define {} BORINGSSL_ADD_PREFIX(BORINGSSL_PREFIX, {})\n",
+            symbol, symbol
+        ));
+    }
+    header
 }
 
-fn validate_dependencies() {
-    let go = have_go();
+fn read_symbol_file(path: &str) -> Option<Vec<String>> {
+    let symbols = fs::read_to_string(path).ok()?;
+    Some(
+        symbols
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect(),
+    )
+}
+
+/// Lists symbols defined by prefixed library, without the prefix.
+fn prefixed_symbols(library: &str) -> Vec<String> {
+    let prefix = format!(
+        "{}__SOTER_BORINGSSL_{}_",
+        symbol_underscore(),
+        version_string()
+    );
+    defined_symbols(library)
+        .iter()
+        .filter_map(|symbol| symbol.strip_prefix(&prefix))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Computes a key identifying the build in cache.
+///
+/// Builds are reusable if they are made from the same BoringSSL revision, for the same
+/// target, with the same configuration and compilers. BoringSSL is built the same way
+/// for all Cargo profiles, so debug and release builds share the cache entry.
+fn build_cache_key(abs_boringssl_src: &str, target_flags: &[String], fips: bool) -> String {
+    // Released crates always vendor the same BoringSSL, so the revision may be missing.
+    // Check for ".git" explicitly, otherwise Git might look into the parent repository.
+    let revision = Some(abs_boringssl_src)
+        .filter(|src| Path::new(&format!("{}/.git", src)).exists())
+        .and_then(|src| {
+            Command::new("git")
+                .args(["rev-parse", "HEAD"])
+                .current_dir(src)
+                .output()
+                .ok()
+        })
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        .unwrap_or_default();
+
+    let target = env("TARGET");
+    let mut inputs = vec![
+        version_string(),
+        revision,
+        target.clone(),
+        env("HOST"),
+        fips.to_string(),
+    ];
+    // Target flags include compilers and their flags too.
+    inputs.extend(target_flags.iter().cloned());
+
+    format!(
+        "{}-{}-{:016x}",
+        version_string(),
+        target,
+        fnv1a(&inputs.join("\n"))
+    )
+}
+
+/// 64-bit FNV-1a hash. Unlike std hashers, it is stable across Rust versions.
+fn fnv1a(data: &str) -> u64 {
+    data.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Checks that BoringSSL source code checkout is usable.
+fn validate_boringssl_source(src: &str) {
+    // BoringSSL supports symbol prefixing since 2019. There is no version number
+    // to check, but older CMakeLists.txt do not mention the option at all.
+    let cmake_lists = format!("{}/CMakeLists.txt", src);
+    let supports_prefixing = fs::read_to_string(&cmake_lists)
+        .map(|contents| contents.contains("BORINGSSL_PREFIX"))
+        .unwrap_or_else(|err| {
+            panic!(
+                "
+
+Cannot read {}: {}
+Make sure that {} points to BoringSSL source code checkout.
+
+",
+                cmake_lists, err, CUSTOM_BORINGSSL_SRC
+            )
+        });
+    if !supports_prefixing {
+        panic!(
+            "
+
+BoringSSL in {} does not support symbol prefixing (BORINGSSL_PREFIX).
+Use a newer BoringSSL revision.
+
+",
+            src
+        );
+    }
+}
+
+/// Checks that the library defines all whitelisted functions with the prefix.
+fn validate_prefixed_symbols(library: &str, prefix: &str) {
+    if !have(&nm(), &["--version"]) {
+        println!("cargo:warning=nm not found, cannot check BoringSSL symbols");
+        return;
+    }
+    let whitelist = format!("{}/{}", env("CARGO_MANIFEST_DIR"), WHITELIST);
+    let whitelist = fs::read_to_string(whitelist).expect("failed to read whitelist");
+    let symbols = defined_symbols(library);
+    let missing: Vec<&str> = whitelist
+        .lines()
+        .filter_map(|line| line.strip_suffix("()"))
+        .filter(|name| {
+            let symbol = format!("{}{}_{}", symbol_underscore(), prefix, name);
+            !symbols.contains(&symbol)
+        })
+        .collect();
+    if !missing.is_empty() {
+        panic!(
+            "
+
+BoringSSL built from {} is incompatible with this crate.
+Missing prefixed functions: {}
+
+",
+            CUSTOM_BORINGSSL_SRC,
+            missing.join(", ")
+        );
+    }
+}
+
+/// Downloads prebuilt library and checks its integrity.
+///
+/// Libraries are located at "{base_url}/{version}/{target}/{library}". FIPS builds use
+/// "{target}-fips" instead. Expected checksums are listed in "prebuilt.sha256" file,
+/// or can be provided by SOTER_BORINGSSL_PREBUILT_SHA256 for self-hosted libraries.
+///
+/// Returns false if the library cannot be downloaded, so it should be built instead.
+fn use_prebuilt_boringssl(base_url: &str, soter_crypto_lib: &str, fips: bool) -> bool {
+    let target = env("TARGET");
+    let flavor = if fips {
+        format!("{}-fips", target)
+    } else {
+        target
+    };
+    let artifact = format!("{}/{}/{}", version_string(), flavor, soter_crypto_lib);
+
+    let expected_checksum = optional_env(PREBUILT_SHA256)
+        .or_else(|| known_checksum(&artifact))
+        .unwrap_or_else(|| {
+            panic!(
+                "
+
+No known checksum for prebuilt {}.
+Set {} to the expected SHA-256 checksum, or unset {} to build BoringSSL.
+
+",
+                artifact, PREBUILT_SHA256, PREBUILT_URL
+            )
+        })
+        .to_lowercase();
+
+    let abs_prebuilt_dir = format!("{}/{}", env("OUT_DIR"), PREBUILT_DIR);
+    let library = format!("{}/{}", abs_prebuilt_dir, soter_crypto_lib);
+    let download = format!("{}.download", library);
+    fs::create_dir_all(&abs_prebuilt_dir).expect("failed to create prebuilt directory");
+
+    // Local mirrors can be used with "file://" URLs, they are available offline too.
+    let url = format!("{}/{}", base_url.trim_end_matches('/'), artifact);
+    let downloaded = if let Some(path) = url.strip_prefix("file://") {
+        fs::copy(path, &download).is_ok()
+    } else if offline() {
+        println!("cargo:warning=offline mode, building BoringSSL instead of downloading");
+        return false;
+    } else {
+        Command::new("curl")
+            .args(["--fail", "--silent", "--show-error", "--location"])
+            .args(["--output", &download, &url])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    };
+    if !downloaded {
+        let _ = fs::remove_file(&download);
+        println!(
+            "cargo:warning=failed to download {}, building BoringSSL",
+            url
+        );
+        return false;
+    }
+
+    let data = fs::read(&download).expect("failed to read downloaded library");
+    let actual_checksum = sha256_hex(&data);
+    if actual_checksum != expected_checksum {
+        let _ = fs::remove_file(&download);
+        panic!(
+            "
+
+Checksum mismatch for {}
+Expected SHA-256: {}
+Actual SHA-256:   {}
+
+",
+            url, expected_checksum, actual_checksum
+        );
+    }
+    fs::rename(&download, &library).expect("failed to store downloaded library");
+
+    true
+}
+
+/// Looks up expected checksum of prebuilt library in "prebuilt.sha256".
+fn known_checksum(artifact: &str) -> Option<String> {
+    let checksums = format!("{}/{}", env("CARGO_MANIFEST_DIR"), PREBUILT_CHECKSUMS);
+    println!("cargo:rerun-if-changed={}", checksums);
+    // Same format as "sha256sum" output, with comments.
+    fs::read_to_string(checksums)
+        .ok()?
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?, fields.next()?))
+        })
+        .find(|&(_, path)| path == artifact)
+        .map(|(checksum, _)| checksum.to_owned())
+}
+
+fn offline() -> bool {
+    [OFFLINE, "CARGO_NET_OFFLINE"]
+        .iter()
+        .filter_map(|var| optional_env(var))
+        .any(|value| value == "1" || value == "true")
+}
+
+/// Computes SHA-256 of the data as a lowercase hex string.
+///
+/// Build scripts have no dependencies, so here is a simple implementation of FIPS 180-4.
+fn sha256_hex(data: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (state, value) in h.iter_mut().zip(&[a, b, c, d, e, f, g, hh]) {
+            *state = state.wrapping_add(*value);
+        }
+    }
+
+    h.iter().map(|word| format!("{:08x}", word)).collect()
+}
+
+/// Copies build artifacts into cache.
+///
+/// The files are first copied into a temporary directory which is then atomically renamed,
+/// so that concurrent builds never see incomplete entries. Failures are not fatal.
+fn store_in_cache(cache_dir: &str, files: &[(&str, &str)]) {
+    let tmp_dir = format!("{}.tmp{}", cache_dir, std::process::id());
+    let result = fs::create_dir_all(&tmp_dir)
+        .and_then(|_| {
+            for (from, name) in files {
+                fs::copy(from, format!("{}/{}", tmp_dir, name))?;
+            }
+            Ok(())
+        })
+        .and_then(|_| fs::rename(&tmp_dir, cache_dir));
+    if let Err(err) = result {
+        // Most likely a concurrent build has already stored the same entry.
+        let _ = fs::remove_dir_all(&tmp_dir);
+        if !Path::new(cache_dir).is_dir() {
+            println!(
+                "cargo:warning=failed to store BoringSSL build in cache: {}",
+                err
+            );
+        }
+    }
+}
+
+/// Returns CMake flags needed to build for the target platform.
+fn target_cmake_flags() -> Vec<String> {
+    // Custom toolchain file replaces our own cross-compilation setup.
+    let mut flags = match optional_env(CMAKE_TOOLCHAIN_FILE) {
+        Some(toolchain_file) => {
+            println!("cargo:rerun-if-changed={}", toolchain_file);
+            vec![format!("-DCMAKE_TOOLCHAIN_FILE={}", toolchain_file)]
+        }
+        None => match env("CARGO_CFG_TARGET_OS").as_str() {
+            "android" => android_cmake_flags(),
+            "ios" => ios_cmake_flags(),
+            "macos" => macos_cmake_flags(),
+            "linux" if env("CARGO_CFG_TARGET_ENV") == "musl" => musl_cmake_flags(),
+            "windows" if env("CARGO_CFG_TARGET_ENV") == "gnu" => mingw_cmake_flags(),
+            _ => vec![],
+        },
+    };
+    flags.extend(compiler_cmake_flags());
+    if no_asm() {
+        flags.push("-DOPENSSL_NO_ASM=1".to_owned());
+    }
+    flags
+}
+
+/// Returns true if BoringSSL should be built without assembly, using only C code.
+fn no_asm() -> bool {
+    env::var("CARGO_FEATURE_NO_ASM").is_ok()
+        || matches!(optional_env(NO_ASM).as_deref(), Some("1") | Some("true"))
+}
+
+/// Returns CMake flags for compilers and compiler flags set in the environment.
+///
+/// CMake looks at CC, CXX, CFLAGS, CXXFLAGS only when configuring a new build directory,
+/// and it does not know about target-specific variants of these variables.
+fn compiler_cmake_flags() -> Vec<String> {
+    let mut flags = vec![];
+    for (tool, cmake_variable) in &[
+        ("CC", "CMAKE_C_COMPILER"),
+        ("CXX", "CMAKE_CXX_COMPILER"),
+        ("CFLAGS", "CMAKE_C_FLAGS"),
+        ("CXXFLAGS", "CMAKE_CXX_FLAGS"),
+    ] {
+        if let Some(value) = target_tool(tool) {
+            flags.push(format!("-D{}={}", cmake_variable, value));
+        }
+    }
+    flags
+}
+
+/// Returns CMake flags for building with musl libc.
+///
+/// PIC code is already enabled for all builds, so the resulting libcrypto.a
+/// is suitable for both static and static-PIE executables.
+fn musl_cmake_flags() -> Vec<String> {
+    let mut flags = vec![];
+    // If the compiler is not set explicitly (see compiler_cmake_flags), use musl-gcc
+    // wrapper when cross-compiling from glibc. Native builds (e.g., on Alpine)
+    // use the default compiler.
+    let cross_compiling = env("HOST") != env("TARGET");
+    if cross_compiling && target_tool("CC").is_none() {
+        flags.push("-DCMAKE_C_COMPILER=musl-gcc".to_owned());
+    }
+    // BoringSSL selects assembly implementation based on the processor.
+    // Build scripts run on the host so their ARCH is the host architecture.
+    let target_arch = env("CARGO_CFG_TARGET_ARCH");
+    if target_arch != env::consts::ARCH {
+        flags.push("-DCMAKE_SYSTEM_NAME=Linux".to_owned());
+        flags.push(format!("-DCMAKE_SYSTEM_PROCESSOR={}", target_arch));
+    }
+    flags
+}
+
+/// Returns CMake flags for building with MinGW toolchain, natively or cross-compiling.
+fn mingw_cmake_flags() -> Vec<String> {
+    let mut flags = vec![];
+    // If the compiler is not set explicitly (see compiler_cmake_flags), use MinGW-w64
+    // cross-compilers which are usually available as "x86_64-w64-mingw32-gcc", etc.
+    if env("HOST") != env("TARGET") {
+        let target = env("TARGET");
+        let arch = target.split('-').next().unwrap_or_default();
+        flags.push("-DCMAKE_SYSTEM_NAME=Windows".to_owned());
+        flags.push(format!(
+            "-DCMAKE_SYSTEM_PROCESSOR={}",
+            env("CARGO_CFG_TARGET_ARCH")
+        ));
+        if target_tool("CC").is_none() {
+            flags.push(format!("-DCMAKE_C_COMPILER={}-w64-mingw32-gcc", arch));
+        }
+        if target_tool("CXX").is_none() {
+            flags.push(format!("-DCMAKE_CXX_COMPILER={}-w64-mingw32-g++", arch));
+        }
+    }
+    // BoringSSL assembly for Windows needs NASM, MinGW assembler cannot build it.
+    if !no_asm() && !have("nasm", &["-v"]) {
+        println!("cargo:warning=NASM not found, building BoringSSL without assembly");
+        flags.push("-DOPENSSL_NO_ASM=1".to_owned());
+    }
+    flags
+}
+
+/// Returns target-specific tool override, following "cc" crate conventions.
+///
+/// For example, for CC and x86_64-unknown-linux-musl target this checks
+/// CC_x86_64-unknown-linux-musl, CC_x86_64_unknown_linux_musl, TARGET_CC, CC.
+fn target_tool(name: &str) -> Option<String> {
+    let target = env("TARGET");
+    [
+        format!("{}_{}", name, target),
+        format!("{}_{}", name, target.replace('-', "_")),
+        format!("TARGET_{}", name),
+        name.to_owned(),
+    ]
+    .iter()
+    .find_map(|var| optional_env(var))
+}
+
+// Default iOS deployment targets, if not specified explicitly with IPHONEOS_DEPLOYMENT_TARGET.
+// Simulators on Apple Silicon are supported only since iOS 14.
+const DEFAULT_IOS_DEPLOYMENT_TARGET: &str = "10.0";
+const DEFAULT_IOS_ARM64_SIMULATOR_DEPLOYMENT_TARGET: &str = "14.0";
+
+/// Returns CMake flags for cross-compilation to iOS devices and simulators.
+fn ios_cmake_flags() -> Vec<String> {
+    let arch = apple_arch();
+    // Intel simulator has its own architecture, ARM simulator is a separate "-sim" target.
+    let simulator = arch == "x86_64" || env("TARGET").ends_with("-sim");
+    let sysroot = if simulator {
+        "iphonesimulator"
+    } else {
+        "iphoneos"
+    };
+    let deployment_target = optional_env("IPHONEOS_DEPLOYMENT_TARGET").unwrap_or_else(|| {
+        if simulator && arch == "arm64" {
+            DEFAULT_IOS_ARM64_SIMULATOR_DEPLOYMENT_TARGET.to_owned()
+        } else {
+            DEFAULT_IOS_DEPLOYMENT_TARGET.to_owned()
+        }
+    });
+
+    let mut flags = vec![
+        "-DCMAKE_SYSTEM_NAME=iOS".to_owned(),
+        format!("-DCMAKE_OSX_SYSROOT={}", sysroot),
+        format!("-DCMAKE_OSX_ARCHITECTURES={}", arch),
+        format!("-DCMAKE_OSX_DEPLOYMENT_TARGET={}", deployment_target),
+    ];
+    // Some apps still need bitcode for App Store submission. It is not used by simulators.
+    if !simulator && optional_env("SOTER_BORINGSSL_BITCODE").as_deref() == Some("1") {
+        for lang in &["C", "CXX", "ASM"] {
+            flags.push(format!("-DCMAKE_{}_FLAGS=-fembed-bitcode", lang));
+        }
+    }
+    flags
+}
+
+/// Returns CMake flags for macOS, which might be cross-compilation between Intel and ARM.
+fn macos_cmake_flags() -> Vec<String> {
+    // CMake picks up MACOSX_DEPLOYMENT_TARGET from environment by itself.
+    vec![format!("-DCMAKE_OSX_ARCHITECTURES={}", apple_arch())]
+}
+
+/// Returns architecture name as Apple tools call it.
+fn apple_arch() -> &'static str {
+    match env("CARGO_CFG_TARGET_ARCH").as_str() {
+        "aarch64" => "arm64",
+        "x86_64" => "x86_64",
+        arch => panic!("unsupported Apple architecture: {}", arch),
+    }
+}
+
+// Default Android API level, if not specified explicitly.
+// This is the minimum API level supported by NDK for 64-bit targets.
+const DEFAULT_ANDROID_API_LEVEL: &str = "21";
+
+/// Returns CMake flags for cross-compilation with Android NDK.
+///
+/// NDK is located via ANDROID_NDK_HOME (set by cargo-ndk), ANDROID_NDK_ROOT, or NDK_HOME.
+/// API level is taken from CARGO_NDK_ANDROID_PLATFORM (set by cargo-ndk) or ANDROID_PLATFORM.
+fn android_cmake_flags() -> Vec<String> {
+    let ndk = ["ANDROID_NDK_HOME", "ANDROID_NDK_ROOT", "NDK_HOME"]
+        .iter()
+        .find_map(|name| optional_env(name))
+        .unwrap_or_else(|| {
+            panic!(
+                "
+
+Building for Android requires Android NDK.
+Set ANDROID_NDK_HOME environment variable to point to NDK location.
+
+"
+            )
+        });
+    let toolchain = format!("{}/build/cmake/android.toolchain.cmake", ndk);
+    if !Path::new(&toolchain).is_file() {
+        panic!("cannot find NDK CMake toolchain file: {}", toolchain);
+    }
+
+    let abi = match env("CARGO_CFG_TARGET_ARCH").as_str() {
+        "aarch64" => "arm64-v8a",
+        "arm" => "armeabi-v7a",
+        "x86" => "x86",
+        "x86_64" => "x86_64",
+        arch => panic!("unsupported Android architecture: {}", arch),
+    };
+
+    let api_level = ["CARGO_NDK_ANDROID_PLATFORM", "ANDROID_PLATFORM"]
+        .iter()
+        .find_map(|name| optional_env(name))
+        .unwrap_or_else(|| DEFAULT_ANDROID_API_LEVEL.to_owned());
+    // Both "21" and "android-21" are accepted.
+    let api_level = api_level.trim_start_matches("android-");
+
+    vec![
+        format!("-DCMAKE_TOOLCHAIN_FILE={}", toolchain),
+        format!("-DANDROID_ABI={}", abi),
+        format!("-DANDROID_PLATFORM=android-{}", api_level),
+    ]
+}
+
+/// Uses BoringSSL built elsewhere, skipping the vendored build.
+///
+/// Its libcrypto is expected in "lib" subdirectory (installation prefix) or in "crypto"
+/// subdirectory (CMake build directory). The library must be built without prefixes.
+/// We make a copy of it with the same symbol prefix that a vendored build would have.
+fn use_system_boringssl(boringssl_dir: &str) {
+    validate_system_dependencies();
+
+    let crypto = ["lib", "crypto"]
+        .iter()
+        .map(|dir| format!("{}/{}/{}", boringssl_dir, dir, lib("crypto")))
+        .find(|path| Path::new(path).is_file())
+        .unwrap_or_else(|| {
+            panic!(
+                "
+
+Cannot find BoringSSL library {} in {}/lib or {}/crypto.
+Make sure that {} points to BoringSSL build or installation directory.
+
+",
+                lib("crypto"),
+                boringssl_dir,
+                boringssl_dir,
+                SYSTEM_BORINGSSL_DIR
+            )
+        });
+    println!("cargo:rerun-if-changed={}", crypto);
+
+    let out_dir = env("OUT_DIR");
+    let abs_build_dir = format!("{}/{}", out_dir, SYSTEM_BUILD_DIR);
+    let abs_symbol_map = format!("{}/{}", out_dir, SYSTEM_SYMBOL_MAP);
+
+    fs::create_dir_all(&abs_build_dir).expect("failed to create build directory");
+
+    let version_string = version_string();
+    let prefix = format!("__SOTER_BORINGSSL_{}", version_string);
+
+    // Some targets have an extra leading underscore in symbols, the prefix goes after it.
+    let underscore = symbol_underscore();
+    let mut symbol_map = String::new();
+    let mut symbols = vec![];
+    for symbol in defined_symbols(&crypto) {
+        if let Some(name) = symbol.strip_prefix(underscore) {
+            symbol_map.push_str(&format!("{} {}{}_{}\n", symbol, underscore, prefix, name));
+            symbols.push(name.to_owned());
+        }
+    }
+    fs::write(&abs_symbol_map, symbol_map).expect("failed to write symbol map");
+
+    let soter_crypto = format!("soter_crypto_{}", version_string);
+    let soter_crypto = format!("{}/{}", abs_build_dir, lib(&soter_crypto));
+
+    run(
+        &objcopy(),
+        &[
+            &format!("--redefine-syms={}", abs_symbol_map),
+            &crypto,
+            &soter_crypto,
+        ],
+    );
+
+    // Bindings are generated for the vendored BoringSSL, use its headers too.
+    let abs_boringssl_src = format!("{}/{}", env("CARGO_MANIFEST_DIR"), BORINGSSL_SRC);
+    export_library(&abs_build_dir, &abs_boringssl_src, Some(symbols));
+}
+
+/// Writes a stand-in for Go which outputs pre-generated err_data.c, returns its path.
+///
+/// BoringSSL runs "go run err_data_generate.go > err_data.c" to generate the file,
+/// the arguments are simply ignored.
+fn write_go_shim(shim_dir: &str, err_data: &str) -> String {
+    fs::create_dir_all(shim_dir).expect("failed to create Go shim directory");
+
+    #[cfg(windows)]
+    let (shim, script) = (
+        format!("{}/go.bat", shim_dir),
+        format!("@type \"{}\"\r\n", err_data.replace('/', "\\")),
+    );
+    #[cfg(not(windows))]
+    let (shim, script) = (
+        format!("{}/go", shim_dir),
+        format!(
+            "#!/bin/sh\nexec cat '{}'\n",
+            err_data.replace('\'', "'\\''")
+        ),
+    );
+
+    fs::write(&shim, script).expect("failed to write Go shim");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&shim, fs::Permissions::from_mode(0o755))
+            .expect("failed to make Go shim executable");
+    }
+
+    shim
+}
+
+/// Writes the list of symbols for BoringSSL prefixing, like "util/read_symbols.go" does.
+fn write_symbol_file(symbol_file: &str, library: &str) {
+    // Some targets have an extra leading underscore in symbols, BoringSSL expects names without it.
+    let underscore = symbol_underscore();
+    let mut symbols = String::new();
+    for symbol in defined_symbols(library) {
+        if let Some(name) = symbol.strip_prefix(underscore) {
+            symbols.push_str(name);
+            symbols.push('\n');
+        }
+    }
+    fs::write(symbol_file, symbols).expect("failed to write symbol file");
+}
+
+/// Returns the prefix that the target adds to C symbol names.
+///
+/// Mach-O and 32-bit Windows (COFF) symbols have an extra leading underscore.
+fn symbol_underscore() -> &'static str {
+    let apple = env("CARGO_CFG_TARGET_VENDOR") == "apple";
+    let win32 = env("CARGO_CFG_TARGET_OS") == "windows" && env("CARGO_CFG_TARGET_ARCH") == "x86";
+    if apple || win32 {
+        "_"
+    } else {
+        ""
+    }
+}
+
+fn version_string() -> String {
+    let major = env("CARGO_PKG_VERSION_MAJOR");
+    let minor = env("CARGO_PKG_VERSION_MINOR");
+    let patch = env("CARGO_PKG_VERSION_PATCH");
+    format!("{}_{}_{}", major, minor, patch)
+}
+
+/// Lists external symbols defined by the library.
+fn defined_symbols(library: &str) -> BTreeSet<String> {
+    let output = Command::new(nm())
+        .args(["--defined-only", "--extern-only", "--format=posix", library])
+        .stderr(Stdio::inherit())
+        .output()
+        .unwrap_or_else(|err| panic!("failed to invoke nm: {}", err));
+    if !output.status.success() {
+        panic!("nm failed with status {}", output.status);
+    }
+    // Symbol lines look like "name type [value size]". Object file headers look like
+    // "libcrypto.a[file.o]:" and have only one field, skip them.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            fields.next()?;
+            Some(name.to_owned())
+        })
+        .collect()
+}
+
+fn validate_system_dependencies() {
+    if !have(&nm(), &["--version"]) || !have(&objcopy(), &["--version"]) {
+        panic!(
+            "
+
+Using BoringSSL from {} requires nm and objcopy tools (GNU or LLVM).
+Set NM and OBJCOPY environment variables if they are not in PATH.
+
+",
+            SYSTEM_BORINGSSL_DIR
+        );
+    }
+}
+
+fn nm() -> String {
+    optional_env("NM").unwrap_or_else(|| "nm".to_owned())
+}
+
+fn objcopy() -> String {
+    optional_env("OBJCOPY").unwrap_or_else(|| "objcopy".to_owned())
+}
+
+fn validate_dependencies(go: bool, fips: bool, err_data: &str) {
     let cmake = have_cmake();
     let ninja = have_ninja();
     let make = have_make();
 
-    if !go {
+    if !go && fips {
+        panic!(
+            "
+
+Missing build dependency Go (1.11 or higher). It is required to build FIPS module.
+
+"
+        );
+    }
+    if !go && !Path::new(err_data).is_file() {
         panic!(
             "
 
 Missing build dependency Go (1.11 or higher).
 
+Alternatively, set {} to the path of pre-generated err_data.c
+matching the vendored BoringSSL revision (see err_data.sh).
+
+",
+            ERR_DATA_FILE
+        );
+    }
+    if !go && !have(&nm(), &["--version"]) {
+        panic!(
+            "
+
+Building without Go requires nm tool (GNU or LLVM).
+Set NM environment variable if it is not in PATH.
+
 "
         );
     }
@@ -198,8 +1172,13 @@ fn have(name: &str, args: &[&str]) -> bool {
 }
 
 fn run(cmd: &str, args: &[&str]) {
+    run_with_env(cmd, args, &[]);
+}
+
+fn run_with_env(cmd: &str, args: &[&str], vars: &[(String, String)]) {
     let output = Command::new(cmd)
         .args(args)
+        .envs(vars.iter().map(|(name, value)| (name, value)))
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .output()
@@ -210,6 +1189,28 @@ fn run(cmd: &str, args: &[&str]) {
     }
 }
 
+/// Returns build tool arguments and environment which limit the number of parallel jobs.
+///
+/// SOTER_BUILD_JOBS sets the number of jobs explicitly. Otherwise Make joins Cargo's jobserver,
+/// so that the entire build runs no more jobs than Cargo allows. Ninja cannot use jobservers,
+/// it runs as many jobs as Cargo has allotted to this build script.
+fn job_control(build_with: &BuildSystem) -> (Vec<String>, Vec<(String, String)>) {
+    if let Some(jobs) = optional_env(BUILD_JOBS) {
+        match jobs.parse::<usize>() {
+            Ok(jobs) if jobs > 0 => return (vec![format!("-j{}", jobs)], vec![]),
+            _ => panic!("{} must be a positive number, got \"{}\"", BUILD_JOBS, jobs),
+        }
+    }
+    let cargo_jobs = vec![format!("-j{}", env("NUM_JOBS"))];
+    match build_with {
+        BuildSystem::Make => match optional_env("CARGO_MAKEFLAGS") {
+            Some(makeflags) => (vec![], vec![("MAKEFLAGS".to_owned(), makeflags)]),
+            None => (cargo_jobs, vec![]),
+        },
+        BuildSystem::Ninja => (cargo_jobs, vec![]),
+    }
+}
+
 enum BuildSystem {
     Ninja,
     Make,
@@ -234,14 +1235,31 @@ fn symlink_from_to(from: &str, to: &str) -> std::io::Result<()> {
     #[cfg(unix)]
     return std::os::unix::fs::symlink(from, to);
     #[cfg(windows)]
-    return std::os::windows::fs::symlink_file(from, to);
+    return symlink_or_copy(from, to);
     #[cfg(not(any(unix, windows)))]
     return fs::rename(from, to);
 }
 
+/// Windows allows creating symlinks only in Developer Mode or with administrator rights.
+/// Copy the file if we cannot make a symlink. Unlike symlinks, copies get stale when
+/// the library is rebuilt, so they are always refreshed.
+#[cfg(windows)]
+fn symlink_or_copy(from: &str, to: &str) -> std::io::Result<()> {
+    let is_symlink = fs::symlink_metadata(to)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false);
+    if is_symlink {
+        return Err(std::io::ErrorKind::AlreadyExists.into());
+    }
+    if std::os::windows::fs::symlink_file(from, to).is_err() {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
 fn lib(name: &str) -> String {
-    // TODO: ensure that we support both *-pc-windows-msvc and *-pc-windows-gnu
-    if cfg!(windows) {
+    // MSVC names static libraries "name.lib", MinGW uses "libname.a" like Unix systems.
+    if env("CARGO_CFG_TARGET_ENV") == "msvc" {
         format!("{}.lib", name)
     } else {
         format!("lib{}.a", name)
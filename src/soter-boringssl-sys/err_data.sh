@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+
+# Copyright 2020 themis.rs maintainers
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+# Regenerate err_data.c snapshot used for building BoringSSL without Go.
+# Run this script with Go installed after updating the BoringSSL submodule.
+
+set -e
+
+OUTPUT="$PWD/generated/err_data.c"
+
+mkdir -p "$(dirname "$OUTPUT")"
+
+TMP="$(mktemp)"
+(cd boringssl/crypto/err && go run err_data_generate.go) > "$TMP"
+mv "$TMP" "$OUTPUT"
+
+echo "Generated $OUTPUT for BoringSSL $(git -C boringssl rev-parse --short HEAD)"
@@ -27,10 +27,211 @@ extern "C" {}
 
 /* automatically generated by rust-bindgen */
 
+pub type BN_ULONG = u64;
 pub type BIGNUM = u8;
+pub type BN_CTX = u8;
+pub type BN_GENCB = u8;
+pub type EC_GROUP = u8;
+pub type EC_KEY = u8;
+pub type EC_POINT = u8;
 pub type ENGINE = u8;
+pub type EVP_AEAD = u8;
 pub type EVP_MD_CTX = [u64; 4usize];
 pub type EVP_MD = u8;
+pub type EVP_PKEY_CTX = u8;
+pub type EVP_PKEY = u8;
+pub type HMAC_CTX = [u64; 13usize];
+pub type RSA = u8;
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_aead_aes_128_gcm"]
+    pub fn EVP_aead_aes_128_gcm() -> *const EVP_AEAD;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_aead_aes_256_gcm"]
+    pub fn EVP_aead_aes_256_gcm() -> *const EVP_AEAD;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_aead_chacha20_poly1305"]
+    pub fn EVP_aead_chacha20_poly1305() -> *const EVP_AEAD;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_key_length"]
+    pub fn EVP_AEAD_key_length(aead: *const EVP_AEAD) -> usize;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_nonce_length"]
+    pub fn EVP_AEAD_nonce_length(aead: *const EVP_AEAD) -> usize;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_max_overhead"]
+    pub fn EVP_AEAD_max_overhead(aead: *const EVP_AEAD) -> usize;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_max_tag_len"]
+    pub fn EVP_AEAD_max_tag_len(aead: *const EVP_AEAD) -> usize;
+}
+pub type EVP_AEAD_CTX = [u64; 75usize];
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_CTX_new"]
+    pub fn EVP_AEAD_CTX_new(
+        aead: *const EVP_AEAD,
+        key: *const u8,
+        key_len: usize,
+        tag_len: usize,
+    ) -> *mut EVP_AEAD_CTX;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_CTX_free"]
+    pub fn EVP_AEAD_CTX_free(ctx: *mut EVP_AEAD_CTX);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_CTX_seal"]
+    pub fn EVP_AEAD_CTX_seal(
+        ctx: *const EVP_AEAD_CTX,
+        out: *mut u8,
+        out_len: *mut usize,
+        max_out_len: usize,
+        nonce: *const u8,
+        nonce_len: usize,
+        in_: *const u8,
+        in_len: usize,
+        ad: *const u8,
+        ad_len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_AEAD_CTX_open"]
+    pub fn EVP_AEAD_CTX_open(
+        ctx: *const EVP_AEAD_CTX,
+        out: *mut u8,
+        out_len: *mut usize,
+        max_out_len: usize,
+        nonce: *const u8,
+        nonce_len: usize,
+        in_: *const u8,
+        in_len: usize,
+        ad: *const u8,
+        ad_len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_new"]
+    pub fn BN_new() -> *mut BIGNUM;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_free"]
+    pub fn BN_free(bn: *mut BIGNUM);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_num_bits"]
+    pub fn BN_num_bits(bn: *const BIGNUM) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_bin2bn"]
+    pub fn BN_bin2bn(in_: *const u8, len: usize, ret: *mut BIGNUM) -> *mut BIGNUM;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_set_word"]
+    pub fn BN_set_word(bn: *mut BIGNUM, value: BN_ULONG) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_num_bytes"]
+    pub fn BN_num_bytes(bn: *const BIGNUM) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_bn2bin_padded"]
+    pub fn BN_bn2bin_padded(out: *mut u8, len: usize, in_: *const BIGNUM) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_CTX_new"]
+    pub fn BN_CTX_new() -> *mut BN_CTX;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_BN_CTX_free"]
+    pub fn BN_CTX_free(ctx: *mut BN_CTX);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_FIPS_mode"]
+    pub fn FIPS_mode() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_POINT_new"]
+    pub fn EC_POINT_new(group: *const EC_GROUP) -> *mut EC_POINT;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_POINT_free"]
+    pub fn EC_POINT_free(point: *mut EC_POINT);
+}
+pub type point_conversion_form_t = u32;
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_POINT_point2oct"]
+    pub fn EC_POINT_point2oct(
+        group: *const EC_GROUP,
+        point: *const EC_POINT,
+        form: point_conversion_form_t,
+        buf: *mut u8,
+        len: usize,
+        ctx: *mut BN_CTX,
+    ) -> usize;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_POINT_oct2point"]
+    pub fn EC_POINT_oct2point(
+        group: *const EC_GROUP,
+        point: *mut EC_POINT,
+        buf: *const u8,
+        len: usize,
+        ctx: *mut BN_CTX,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_new_by_curve_name"]
+    pub fn EC_KEY_new_by_curve_name(nid: ::std::os::raw::c_int) -> *mut EC_KEY;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_free"]
+    pub fn EC_KEY_free(key: *mut EC_KEY);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_get0_group"]
+    pub fn EC_KEY_get0_group(key: *const EC_KEY) -> *const EC_GROUP;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_get0_private_key"]
+    pub fn EC_KEY_get0_private_key(key: *const EC_KEY) -> *const BIGNUM;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_get0_public_key"]
+    pub fn EC_KEY_get0_public_key(key: *const EC_KEY) -> *const EC_POINT;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_set_private_key"]
+    pub fn EC_KEY_set_private_key(key: *mut EC_KEY, priv_: *const BIGNUM) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_set_public_key"]
+    pub fn EC_KEY_set_public_key(key: *mut EC_KEY, pub_: *const EC_POINT) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_check_key"]
+    pub fn EC_KEY_check_key(key: *const EC_KEY) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EC_KEY_generate_key"]
+    pub fn EC_KEY_generate_key(key: *mut EC_KEY) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_ERR_get_error"]
+    pub fn ERR_get_error() -> u32;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_ERR_lib_error_string"]
+    pub fn ERR_lib_error_string(packed_error: u32) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_ERR_reason_error_string"]
+    pub fn ERR_reason_error_string(packed_error: u32) -> *const ::std::os::raw::c_char;
+}
 extern "C" {
     #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_sha256"]
     pub fn EVP_sha256() -> *const EVP_MD;
@@ -75,7 +276,280 @@ extern "C" {
     #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_MD_CTX_destroy"]
     pub fn EVP_MD_CTX_destroy(ctx: *mut EVP_MD_CTX);
 }
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_new"]
+    pub fn EVP_PKEY_new() -> *mut EVP_PKEY;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_free"]
+    pub fn EVP_PKEY_free(pkey: *mut EVP_PKEY);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_id"]
+    pub fn EVP_PKEY_id(pkey: *const EVP_PKEY) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_size"]
+    pub fn EVP_PKEY_size(pkey: *const EVP_PKEY) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_assign_RSA"]
+    pub fn EVP_PKEY_assign_RSA(pkey: *mut EVP_PKEY, key: *mut RSA) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_get0_RSA"]
+    pub fn EVP_PKEY_get0_RSA(pkey: *const EVP_PKEY) -> *mut RSA;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_assign_EC_KEY"]
+    pub fn EVP_PKEY_assign_EC_KEY(pkey: *mut EVP_PKEY, key: *mut EC_KEY) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_get0_EC_KEY"]
+    pub fn EVP_PKEY_get0_EC_KEY(pkey: *const EVP_PKEY) -> *mut EC_KEY;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_DigestSignInit"]
+    pub fn EVP_DigestSignInit(
+        ctx: *mut EVP_MD_CTX,
+        pctx: *mut *mut EVP_PKEY_CTX,
+        type_: *const EVP_MD,
+        e: *mut ENGINE,
+        pkey: *mut EVP_PKEY,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_DigestSignUpdate"]
+    pub fn EVP_DigestSignUpdate(
+        ctx: *mut EVP_MD_CTX,
+        data: *const ::std::os::raw::c_void,
+        len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_DigestSignFinal"]
+    pub fn EVP_DigestSignFinal(
+        ctx: *mut EVP_MD_CTX,
+        out_sig: *mut u8,
+        out_sig_len: *mut usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_DigestVerifyInit"]
+    pub fn EVP_DigestVerifyInit(
+        ctx: *mut EVP_MD_CTX,
+        pctx: *mut *mut EVP_PKEY_CTX,
+        type_: *const EVP_MD,
+        e: *mut ENGINE,
+        pkey: *mut EVP_PKEY,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_DigestVerifyUpdate"]
+    pub fn EVP_DigestVerifyUpdate(
+        ctx: *mut EVP_MD_CTX,
+        data: *const ::std::os::raw::c_void,
+        len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_DigestVerifyFinal"]
+    pub fn EVP_DigestVerifyFinal(
+        ctx: *mut EVP_MD_CTX,
+        sig: *const u8,
+        sig_len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_CTX_new"]
+    pub fn EVP_PKEY_CTX_new(pkey: *mut EVP_PKEY, e: *mut ENGINE) -> *mut EVP_PKEY_CTX;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_CTX_free"]
+    pub fn EVP_PKEY_CTX_free(ctx: *mut EVP_PKEY_CTX);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_encrypt_init"]
+    pub fn EVP_PKEY_encrypt_init(ctx: *mut EVP_PKEY_CTX) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_encrypt"]
+    pub fn EVP_PKEY_encrypt(
+        ctx: *mut EVP_PKEY_CTX,
+        out: *mut u8,
+        out_len: *mut usize,
+        in_: *const u8,
+        in_len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_decrypt_init"]
+    pub fn EVP_PKEY_decrypt_init(ctx: *mut EVP_PKEY_CTX) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_decrypt"]
+    pub fn EVP_PKEY_decrypt(
+        ctx: *mut EVP_PKEY_CTX,
+        out: *mut u8,
+        out_len: *mut usize,
+        in_: *const u8,
+        in_len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_derive_init"]
+    pub fn EVP_PKEY_derive_init(ctx: *mut EVP_PKEY_CTX) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_derive_set_peer"]
+    pub fn EVP_PKEY_derive_set_peer(
+        ctx: *mut EVP_PKEY_CTX,
+        peer: *mut EVP_PKEY,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_derive"]
+    pub fn EVP_PKEY_derive(
+        ctx: *mut EVP_PKEY_CTX,
+        key: *mut u8,
+        out_key_len: *mut usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_CTX_set_rsa_padding"]
+    pub fn EVP_PKEY_CTX_set_rsa_padding(
+        ctx: *mut EVP_PKEY_CTX,
+        padding: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_EVP_PKEY_CTX_set_rsa_oaep_md"]
+    pub fn EVP_PKEY_CTX_set_rsa_oaep_md(
+        ctx: *mut EVP_PKEY_CTX,
+        md: *const EVP_MD,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC"]
+    pub fn HMAC(
+        evp_md: *const EVP_MD,
+        key: *const ::std::os::raw::c_void,
+        key_len: usize,
+        data: *const u8,
+        data_len: usize,
+        out: *mut u8,
+        out_len: *mut ::std::os::raw::c_uint,
+    ) -> *mut u8;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC_CTX_new"]
+    pub fn HMAC_CTX_new() -> *mut HMAC_CTX;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC_CTX_free"]
+    pub fn HMAC_CTX_free(ctx: *mut HMAC_CTX);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC_Init_ex"]
+    pub fn HMAC_Init_ex(
+        ctx: *mut HMAC_CTX,
+        key: *const ::std::os::raw::c_void,
+        key_len: usize,
+        md: *const EVP_MD,
+        impl_: *mut ENGINE,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC_Update"]
+    pub fn HMAC_Update(
+        ctx: *mut HMAC_CTX,
+        data: *const u8,
+        data_len: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC_Final"]
+    pub fn HMAC_Final(
+        ctx: *mut HMAC_CTX,
+        out: *mut u8,
+        out_len: *mut ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_HMAC_size"]
+    pub fn HMAC_size(ctx: *const HMAC_CTX) -> usize;
+}
 extern "C" {
     #[link_name = "__SOTER_BORINGSSL_0_1_0_RAND_bytes"]
     pub fn RAND_bytes(buf: *mut u8, len: usize) -> ::std::os::raw::c_int;
 }
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_new"]
+    pub fn RSA_new() -> *mut RSA;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_free"]
+    pub fn RSA_free(rsa: *mut RSA);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_size"]
+    pub fn RSA_size(rsa: *const RSA) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_check_key"]
+    pub fn RSA_check_key(rsa: *const RSA) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_get0_key"]
+    pub fn RSA_get0_key(
+        rsa: *const RSA,
+        out_n: *mut *const BIGNUM,
+        out_e: *mut *const BIGNUM,
+        out_d: *mut *const BIGNUM,
+    );
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_get0_factors"]
+    pub fn RSA_get0_factors(rsa: *const RSA, out_p: *mut *const BIGNUM, out_q: *mut *const BIGNUM);
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_get0_crt_params"]
+    pub fn RSA_get0_crt_params(
+        rsa: *const RSA,
+        out_dmp1: *mut *const BIGNUM,
+        out_dmq1: *mut *const BIGNUM,
+        out_iqmp: *mut *const BIGNUM,
+    );
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_set0_key"]
+    pub fn RSA_set0_key(
+        rsa: *mut RSA,
+        n: *mut BIGNUM,
+        e: *mut BIGNUM,
+        d: *mut BIGNUM,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_set0_factors"]
+    pub fn RSA_set0_factors(rsa: *mut RSA, p: *mut BIGNUM, q: *mut BIGNUM)
+        -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_set0_crt_params"]
+    pub fn RSA_set0_crt_params(
+        rsa: *mut RSA,
+        dmp1: *mut BIGNUM,
+        dmq1: *mut BIGNUM,
+        iqmp: *mut BIGNUM,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "__SOTER_BORINGSSL_0_1_0_RSA_generate_key_ex"]
+    pub fn RSA_generate_key_ex(
+        rsa: *mut RSA,
+        bits: ::std::os::raw::c_int,
+        e: *const BIGNUM,
+        cb: *mut BN_GENCB,
+    ) -> ::std::os::raw::c_int;
+}
@@ -14,7 +14,7 @@
 
 //! Sanity check for the generated bindings.
 
-use soter_boringssl_sys::RAND_bytes;
+use soter_boringssl_sys::{EVP_sha256, RAND_bytes, HMAC};
 
 #[test]
 fn check_csprng() {
@@ -22,3 +22,31 @@ fn check_csprng() {
     let result = unsafe { RAND_bytes(random_data.as_mut_ptr(), random_data.len()) };
     assert_eq!(result, 1);
 }
+
+#[test]
+fn check_hmac() {
+    // RFC 4231, test case 2
+    let key = b"Jefe";
+    let data = b"what do ya want for nothing?";
+    let expected = [
+        0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75,
+        0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec,
+        0x38, 0x43,
+    ];
+    let mut mac = [0; 32];
+    let mut mac_len = 0;
+    let result = unsafe {
+        HMAC(
+            EVP_sha256(),
+            key.as_ptr() as *const _,
+            key.len(),
+            data.as_ptr(),
+            data.len(),
+            mac.as_mut_ptr(),
+            &mut mac_len,
+        )
+    };
+    assert!(!result.is_null());
+    assert_eq!(mac_len as usize, mac.len());
+    assert_eq!(mac, expected);
+}
@@ -0,0 +1,82 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+
+use crate::error::{Error, ErrorKind, Result, ResultExt};
+
+/// Reference to a big number owned by some other object.
+pub struct BIGNUM<'a> {
+    ptr: *const boringssl::BIGNUM,
+    _owner: PhantomData<&'a ()>,
+}
+
+impl<'a> BIGNUM<'a> {
+    /// Wraps a possibly null pointer into a reference.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for lifetime 'a.
+    pub(crate) unsafe fn from_ptr(ptr: *const boringssl::BIGNUM) -> Option<BIGNUM<'a>> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(BIGNUM {
+                ptr,
+                _owner: PhantomData,
+            })
+        }
+    }
+}
+
+/// Owned big number.
+pub(crate) struct OwnedBIGNUM(*mut boringssl::BIGNUM);
+
+impl OwnedBIGNUM {
+    pub(crate) fn as_ptr(&self) -> *const boringssl::BIGNUM {
+        self.0
+    }
+}
+
+impl Drop for OwnedBIGNUM {
+    fn drop(&mut self) {
+        unsafe { boringssl::BN_free(self.0) }
+    }
+}
+
+/// Allocates a new big number with given value.
+pub(crate) fn BN_new_word(value: u64) -> Result<OwnedBIGNUM> {
+    let bn = unsafe { boringssl::BN_new() };
+    if bn.is_null() {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    let bn = OwnedBIGNUM(bn);
+    unsafe { boringssl::BN_set_word(bn.0, value).default_error()? };
+    Ok(bn)
+}
+
+/// Returns the minimum number of bytes needed to represent the absolute value of `bn`.
+pub fn BN_num_bytes(bn: &BIGNUM<'_>) -> usize {
+    unsafe { boringssl::BN_num_bytes(bn.ptr) as usize }
+}
+
+/// Serialises the absolute value of `bn` as a big-endian integer.
+///
+/// The output is padded with leading zeros to fill the entire buffer.
+/// An error is returned if the number does not fit.
+pub fn BN_bn2bin_padded(buffer: &mut [u8], bn: &BIGNUM<'_>) -> Result<()> {
+    unsafe {
+        boringssl::BN_bn2bin_padded(buffer.as_mut_ptr(), buffer.len(), bn.ptr).default_error()
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Name of the library.
+pub const BACKEND_NAME: &str = "BoringSSL";
+
+/// Returns version of the library, if known.
+///
+/// BoringSSL does not have versions, it is identified by commit hash.
+pub fn backend_version() -> Option<&'static str> {
+    None
+}
+
+/// Returns `true` if the library is running in FIPS mode.
+pub fn FIPS_mode() -> bool {
+    unsafe { boringssl::FIPS_mode() == 1 }
+}
@@ -13,8 +13,9 @@
 // limitations under the License.
 
 use std::error;
+use std::ffi::CStr;
 use std::fmt;
-use std::os::raw::c_int;
+use std::os::raw::{c_char, c_int};
 use std::result;
 
 /// Result of BoringSSL function calls.
@@ -24,6 +25,7 @@ pub type Result<T> = result::Result<T, Error>;
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
+    details: Vec<ErrorDetail>,
 }
 
 /// List of BoringSSL error categories.
@@ -39,6 +41,14 @@ pub enum ErrorKind {
     NotSupported,
 }
 
+/// Error reported by BoringSSL in its error queue.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ErrorDetail {
+    code: u32,
+    library: &'static str,
+    reason: &'static str,
+}
+
 impl error::Error for Error {}
 
 impl fmt::Display for Error {
@@ -52,16 +62,77 @@ impl fmt::Display for Error {
     }
 }
 
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.library, self.reason)
+    }
+}
+
 impl Error {
     /// Creates a new error with given kind.
+    ///
+    /// Details are taken from BoringSSL error queue, leaving it empty. Otherwise they would
+    /// be attributed to some unrelated error later.
     pub(crate) fn new(kind: ErrorKind) -> Error {
-        Error { kind }
+        Error {
+            kind,
+            details: take_error_queue(),
+        }
     }
 
     /// Returns the corresponding `ErrorKind` for this error.
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
+
+    /// Returns errors reported by BoringSSL, the earliest one first.
+    pub fn details(&self) -> &[ErrorDetail] {
+        &self.details
+    }
+}
+
+impl ErrorDetail {
+    /// Returns packed BoringSSL error code.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns name of the library which has reported the error.
+    pub fn library(&self) -> &str {
+        self.library
+    }
+
+    /// Returns description of the error.
+    pub fn reason(&self) -> &str {
+        self.reason
+    }
+}
+
+/// Takes all errors from thread-local BoringSSL error queue.
+fn take_error_queue() -> Vec<ErrorDetail> {
+    let mut details = Vec::new();
+    loop {
+        let code = unsafe { boringssl::ERR_get_error() };
+        if code == 0 {
+            break;
+        }
+        details.push(ErrorDetail {
+            code,
+            library: error_string(unsafe { boringssl::ERR_lib_error_string(code) }),
+            reason: error_string(unsafe { boringssl::ERR_reason_error_string(code) }),
+        });
+    }
+    details
+}
+
+fn error_string(string: *const c_char) -> &'static str {
+    if string.is_null() {
+        return "unknown";
+    }
+    // Error strings are static ASCII strings built into the library.
+    unsafe { CStr::from_ptr(string) }
+        .to_str()
+        .unwrap_or("unknown")
 }
 
 /// Conversions to BoringSSL error codes.
@@ -87,3 +158,21 @@ impl ResultExt for c_int {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ec::EC_KEY_new_by_curve_name;
+
+    #[test]
+    fn error_queue_details() {
+        // Unknown curve
+        let err = EC_KEY_new_by_curve_name(0).map(|_| ()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Failure);
+        assert!(!err.details().is_empty());
+        // Error queue is empty now
+        let err = Error::new(ErrorKind::Failure);
+        assert!(err.details().is_empty());
+    }
+}
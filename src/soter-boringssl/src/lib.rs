@@ -15,13 +15,27 @@
 // We follow BoringSSL naming convention, allow it.
 #![allow(non_snake_case)]
 
+mod bn;
+mod crypto;
+mod ec;
 mod error;
 mod hash;
 mod rand;
+mod rsa;
 
-pub use error::{Error, ErrorKind, Result};
+pub use bn::{BN_bn2bin_padded, BN_num_bytes, BIGNUM};
+pub use crypto::{backend_version, FIPS_mode, BACKEND_NAME};
+pub use ec::{
+    point_conversion_form_t, EC_KEY_generate_key, EC_KEY_get0_group, EC_KEY_get0_private_key,
+    EC_KEY_get0_public_key, EC_KEY_new_by_curve_name, EC_POINT_point2oct, NID_X9_62_prime256v1,
+    EC_GROUP, EC_KEY, EC_POINT,
+};
+pub use error::{Error, ErrorDetail, ErrorKind, Result};
 pub use hash::{
     EVP_DigestFinal_ex, EVP_DigestInit, EVP_DigestUpdate, EVP_MD_CTX_create, EVP_MD_CTX_size,
     EVP_sha256, EVP_sha512, EVP_MD, EVP_MD_CTX,
 };
 pub use rand::RAND_bytes;
+pub use rsa::{
+    RSA_generate_key_ex, RSA_get0_crt_params, RSA_get0_factors, RSA_get0_key, RSA_new, RSA,
+};
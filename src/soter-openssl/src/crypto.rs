@@ -0,0 +1,45 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::CStr;
+
+use crate::openssl;
+
+/// Name of the library.
+pub const BACKEND_NAME: &str = "OpenSSL";
+
+/// Returns version of the library, if known.
+///
+/// This is the version of OpenSSL used at runtime, e.g., "OpenSSL 3.0.2 15 Mar 2022".
+pub fn backend_version() -> Option<&'static str> {
+    let version = unsafe { openssl::OpenSSL_version(openssl::OPENSSL_VERSION) };
+    if version.is_null() {
+        return None;
+    }
+    // OpenSSL returns pointers to static strings.
+    unsafe { CStr::from_ptr(version) }.to_str().ok()
+}
+
+/// Returns `true` if the library is running in FIPS mode.
+#[cfg(ossl300)]
+pub fn FIPS_mode() -> bool {
+    // OpenSSL 3.x is in FIPS mode if it fetches algorithms from the FIPS provider by default.
+    unsafe { openssl::EVP_default_properties_is_fips_enabled(std::ptr::null_mut()) == 1 }
+}
+
+/// Returns `true` if the library is running in FIPS mode.
+#[cfg(not(ossl300))]
+pub fn FIPS_mode() -> bool {
+    unsafe { openssl::FIPS_mode() == 1 }
+}
@@ -0,0 +1,149 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+
+use crate::bn::BIGNUM;
+use crate::error::{Error, ErrorKind, Result, ResultExt};
+use crate::openssl;
+
+/// NIST P-256 curve.
+#[allow(non_upper_case_globals)]
+pub const NID_X9_62_prime256v1: c_int = 415;
+
+/// Encoding of elliptic curve points.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum point_conversion_form_t {
+    /// Compressed point encoding: 0x02 or 0x03 byte followed by X coordinate.
+    POINT_CONVERSION_COMPRESSED = 2,
+    /// Uncompressed point encoding: 0x04 byte followed by X and Y coordinates.
+    POINT_CONVERSION_UNCOMPRESSED = 4,
+}
+
+/// Elliptic curve key.
+#[allow(non_camel_case_types)]
+pub struct EC_KEY(*mut openssl::EC_KEY);
+
+// It is possible to move EC_KEY into a different thread. It is also safe
+// to access it concurrently in read-only fashion.
+unsafe impl Send for EC_KEY {}
+unsafe impl Sync for EC_KEY {}
+
+/// Reference to elliptic curve group owned by some other object.
+#[allow(non_camel_case_types)]
+pub struct EC_GROUP<'a> {
+    ptr: *const openssl::EC_GROUP,
+    _owner: PhantomData<&'a ()>,
+}
+
+/// Reference to elliptic curve point owned by some other object.
+#[allow(non_camel_case_types)]
+pub struct EC_POINT<'a> {
+    ptr: *const openssl::EC_POINT,
+    _owner: PhantomData<&'a ()>,
+}
+
+/// Allocates a new elliptic curve key on the given curve.
+pub fn EC_KEY_new_by_curve_name(nid: c_int) -> Result<EC_KEY> {
+    let key = unsafe { openssl::EC_KEY_new_by_curve_name(nid) };
+    if key.is_null() {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    Ok(EC_KEY(key))
+}
+
+impl Drop for EC_KEY {
+    fn drop(&mut self) {
+        unsafe { openssl::EC_KEY_free(self.0) }
+    }
+}
+
+/// Generates a new random private key and computes the corresponding public key.
+pub fn EC_KEY_generate_key(key: &mut EC_KEY) -> Result<()> {
+    unsafe { openssl::EC_KEY_generate_key(key.0).default_error() }
+}
+
+/// Returns the group of the key.
+pub fn EC_KEY_get0_group(key: &EC_KEY) -> EC_GROUP<'_> {
+    // EC_KEY_new_by_curve_name() ensures that the group is always set.
+    EC_GROUP {
+        ptr: unsafe { openssl::EC_KEY_get0_group(key.0) },
+        _owner: PhantomData,
+    }
+}
+
+/// Returns the private key, if it is set.
+pub fn EC_KEY_get0_private_key(key: &EC_KEY) -> Option<BIGNUM<'_>> {
+    unsafe { BIGNUM::from_ptr(openssl::EC_KEY_get0_private_key(key.0)) }
+}
+
+/// Returns the public key, if it is set.
+pub fn EC_KEY_get0_public_key(key: &EC_KEY) -> Option<EC_POINT<'_>> {
+    let ptr = unsafe { openssl::EC_KEY_get0_public_key(key.0) };
+    if ptr.is_null() {
+        return None;
+    }
+    Some(EC_POINT {
+        ptr,
+        _owner: PhantomData,
+    })
+}
+
+/// Encodes the point in the requested form and places it into the buffer.
+///
+/// If the buffer is smaller than needed, an error is returned. If the buffer is bigger,
+/// only a subslice is filled in and returned.
+pub fn EC_POINT_point2oct<'a>(
+    group: &EC_GROUP<'_>,
+    point: &EC_POINT<'_>,
+    form: point_conversion_form_t,
+    buffer: &'a mut [u8],
+) -> Result<&'a mut [u8]> {
+    let form = match form {
+        point_conversion_form_t::POINT_CONVERSION_COMPRESSED => {
+            openssl::point_conversion_form_t::POINT_CONVERSION_COMPRESSED
+        }
+        point_conversion_form_t::POINT_CONVERSION_UNCOMPRESSED => {
+            openssl::point_conversion_form_t::POINT_CONVERSION_UNCOMPRESSED
+        }
+    };
+    let null = std::ptr::null_mut();
+    let no_ctx = std::ptr::null_mut();
+    // First call computes the size without writing anything.
+    let need_size =
+        unsafe { openssl::EC_POINT_point2oct(group.ptr, point.ptr, form, null, 0, no_ctx) };
+    if need_size == 0 {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    if buffer.len() < need_size {
+        return Err(Error::new(ErrorKind::BufferTooSmall(need_size)));
+    }
+    let size = unsafe {
+        openssl::EC_POINT_point2oct(
+            group.ptr,
+            point.ptr,
+            form,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            no_ctx,
+        )
+    };
+    // OpenSSL uses zero return value to indicate errors.
+    if size != need_size {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    Ok(&mut buffer[..size])
+}
@@ -0,0 +1,181 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::{c_char, c_int};
+use std::result;
+
+use crate::openssl;
+
+/// Result of OpenSSL function calls.
+pub type Result<T> = result::Result<T, Error>;
+
+/// OpenSSL error.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    details: Vec<ErrorDetail>,
+}
+
+/// List of OpenSSL error categories.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorKind {
+    /// General failure.
+    Failure,
+    /// Invalid parameter.
+    InvalidParameter,
+    /// Buffer is too small.
+    BufferTooSmall(usize),
+    /// Operation not supported.
+    NotSupported,
+}
+
+/// Error reported by OpenSSL in its error queue.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ErrorDetail {
+    code: u64,
+    library: String,
+    reason: String,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Failure => write!(f, "failure"),
+            ErrorKind::InvalidParameter => write!(f, "invalid parameter"),
+            ErrorKind::BufferTooSmall(min) => write!(f, "buffer too small, need {} bytes", min),
+            ErrorKind::NotSupported => write!(f, "operation not supported"),
+        }
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.library, self.reason)
+    }
+}
+
+impl Error {
+    /// Creates a new error with given kind.
+    ///
+    /// Details are taken from OpenSSL error queue, leaving it empty. Otherwise they would
+    /// be attributed to some unrelated error later.
+    pub(crate) fn new(kind: ErrorKind) -> Error {
+        Error {
+            kind,
+            details: take_error_queue(),
+        }
+    }
+
+    /// Returns the corresponding `ErrorKind` for this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns errors reported by OpenSSL, the earliest one first.
+    pub fn details(&self) -> &[ErrorDetail] {
+        &self.details
+    }
+}
+
+impl ErrorDetail {
+    /// Returns packed OpenSSL error code.
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+
+    /// Returns name of the library which has reported the error.
+    pub fn library(&self) -> &str {
+        &self.library
+    }
+
+    /// Returns description of the error.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// Takes all errors from thread-local OpenSSL error queue.
+fn take_error_queue() -> Vec<ErrorDetail> {
+    let mut details = Vec::new();
+    loop {
+        let code = unsafe { openssl::ERR_get_error() };
+        if code == 0 {
+            break;
+        }
+        details.push(ErrorDetail {
+            code: code as u64,
+            library: error_string(unsafe { openssl::ERR_lib_error_string(code) }),
+            reason: error_string(unsafe { openssl::ERR_reason_error_string(code) }),
+        });
+    }
+    details
+}
+
+fn error_string(string: *const c_char) -> String {
+    if string.is_null() {
+        return "unknown".to_owned();
+    }
+    // Strings of built-in errors are static, but providers may add their own ones,
+    // so make a copy just in case.
+    unsafe { CStr::from_ptr(string) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Conversions to OpenSSL error codes.
+pub trait ResultExt {
+    /// Default OpenSSL error code convention.
+    fn default_error(self) -> Result<()>;
+    /// Operation may not be supported by the system.
+    fn maybe_not_supported(self) -> Result<()>;
+}
+
+impl ResultExt for c_int {
+    fn default_error(self) -> Result<()> {
+        match self {
+            1 => Ok(()),
+            _ => Err(Error::new(ErrorKind::Failure)),
+        }
+    }
+
+    fn maybe_not_supported(self) -> Result<()> {
+        match self {
+            -1 => Err(Error::new(ErrorKind::NotSupported)),
+            _ => self.default_error(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ec::EC_KEY_new_by_curve_name;
+
+    #[test]
+    fn error_queue_details() {
+        // Unknown curve
+        let err = EC_KEY_new_by_curve_name(0).map(|_| ()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Failure);
+        assert!(!err.details().is_empty());
+        // Error queue is empty now
+        let err = Error::new(ErrorKind::Failure);
+        assert!(err.details().is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Soter backend over system OpenSSL.
+//!
+//! This crate provides the same API as `soter-boringssl`, implemented with OpenSSL 1.1.1
+//! or 3.x found in the system. Function names follow BoringSSL conventions where
+//! the libraries differ.
+
+// We follow BoringSSL naming convention, allow it.
+#![allow(non_snake_case)]
+
+use openssl_sys as openssl;
+
+mod bn;
+mod crypto;
+mod ec;
+mod error;
+mod hash;
+mod rand;
+mod rsa;
+
+pub use bn::{BN_bn2bin_padded, BN_num_bytes, BIGNUM};
+pub use crypto::{backend_version, FIPS_mode, BACKEND_NAME};
+pub use ec::{
+    point_conversion_form_t, EC_KEY_generate_key, EC_KEY_get0_group, EC_KEY_get0_private_key,
+    EC_KEY_get0_public_key, EC_KEY_new_by_curve_name, EC_POINT_point2oct, NID_X9_62_prime256v1,
+    EC_GROUP, EC_KEY, EC_POINT,
+};
+pub use error::{Error, ErrorDetail, ErrorKind, Result};
+pub use hash::{
+    EVP_DigestFinal_ex, EVP_DigestInit, EVP_DigestUpdate, EVP_MD_CTX_create, EVP_MD_CTX_size,
+    EVP_sha256, EVP_sha512, EVP_MD, EVP_MD_CTX,
+};
+pub use rand::RAND_bytes;
+pub use rsa::{
+    RSA_generate_key_ex, RSA_get0_crt_params, RSA_get0_factors, RSA_get0_key, RSA_new, RSA,
+};
@@ -0,0 +1,45 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::raw::c_int;
+
+use crate::error::{Result, ResultExt};
+use crate::openssl;
+
+/// Puts cryptographically strong pseudo-random bytes into `buf`.
+pub fn RAND_bytes(buf: &mut [u8]) -> Result<()> {
+    // OpenSSL accepts only int-sized buffers, fill larger ones in chunks.
+    for chunk in buf.chunks_mut(c_int::MAX as usize) {
+        unsafe {
+            openssl::RAND_bytes(chunk.as_mut_ptr(), chunk.len() as c_int).maybe_not_supported()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_buffer() {
+        assert!(RAND_bytes(&mut []).is_ok());
+    }
+
+    #[test]
+    fn normal_buffer() {
+        let mut buffer = [0; 32];
+        assert!(RAND_bytes(&mut buffer).is_ok());
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::raw::c_int;
+
+use crate::bn::{BN_new_word, BIGNUM};
+use crate::error::{Error, ErrorKind, Result, ResultExt};
+use crate::openssl;
+
+/// RSA key.
+pub struct RSA(*mut openssl::RSA);
+
+// It is possible to move RSA into a different thread. It is also safe
+// to access it concurrently in read-only fashion.
+unsafe impl Send for RSA {}
+unsafe impl Sync for RSA {}
+
+/// Allocates a new empty RSA key.
+pub fn RSA_new() -> Result<RSA> {
+    let rsa = unsafe { openssl::RSA_new() };
+    if rsa.is_null() {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    Ok(RSA(rsa))
+}
+
+impl Drop for RSA {
+    fn drop(&mut self) {
+        unsafe { openssl::RSA_free(self.0) }
+    }
+}
+
+/// Generates a new RSA key with a modulus of given size and given public exponent.
+pub fn RSA_generate_key_ex(rsa: &mut RSA, bits: usize, e: u32) -> Result<()> {
+    if bits > c_int::MAX as usize {
+        return Err(Error::new(ErrorKind::InvalidParameter));
+    }
+    let e = BN_new_word(e as u64)?;
+    unsafe {
+        openssl::RSA_generate_key_ex(rsa.0, bits as c_int, e.as_ptr(), std::ptr::null_mut())
+            .default_error()
+    }
+}
+
+/// Returns modulus, public exponent, and private exponent of the key, if they are set.
+pub fn RSA_get0_key(rsa: &RSA) -> (Option<BIGNUM<'_>>, Option<BIGNUM<'_>>, Option<BIGNUM<'_>>) {
+    let null = std::ptr::null();
+    let (mut n, mut e, mut d) = (null, null, null);
+    unsafe {
+        openssl::RSA_get0_key(rsa.0, &mut n, &mut e, &mut d);
+        (
+            BIGNUM::from_ptr(n),
+            BIGNUM::from_ptr(e),
+            BIGNUM::from_ptr(d),
+        )
+    }
+}
+
+/// Returns prime factors of the modulus, if they are set.
+pub fn RSA_get0_factors(rsa: &RSA) -> (Option<BIGNUM<'_>>, Option<BIGNUM<'_>>) {
+    let null = std::ptr::null();
+    let (mut p, mut q) = (null, null);
+    unsafe {
+        openssl::RSA_get0_factors(rsa.0, &mut p, &mut q);
+        (BIGNUM::from_ptr(p), BIGNUM::from_ptr(q))
+    }
+}
+
+/// Returns CRT parameters (dmp1, dmq1, iqmp) of the key, if they are set.
+pub fn RSA_get0_crt_params(
+    rsa: &RSA,
+) -> (Option<BIGNUM<'_>>, Option<BIGNUM<'_>>, Option<BIGNUM<'_>>) {
+    let null = std::ptr::null();
+    let (mut dmp1, mut dmq1, mut iqmp) = (null, null, null);
+    unsafe {
+        openssl::RSA_get0_crt_params(rsa.0, &mut dmp1, &mut dmq1, &mut iqmp);
+        (
+            BIGNUM::from_ptr(dmp1),
+            BIGNUM::from_ptr(dmq1),
+            BIGNUM::from_ptr(iqmp),
+        )
+    }
+}
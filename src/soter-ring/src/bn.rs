@@ -0,0 +1,45 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Big-endian representation of a non-negative integer.
+pub struct BIGNUM<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BIGNUM<'a> {
+    pub(crate) fn from_bytes(bytes: &'a [u8]) -> BIGNUM<'a> {
+        let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        BIGNUM {
+            bytes: &bytes[leading_zeros..],
+        }
+    }
+}
+
+pub fn BN_num_bytes(bn: &BIGNUM<'_>) -> usize {
+    bn.bytes.len()
+}
+
+pub fn BN_bn2bin_padded(buffer: &mut [u8], bn: &BIGNUM<'_>) -> Result<()> {
+    if buffer.len() < bn.bytes.len() {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    let (padding, value) = buffer.split_at_mut(buffer.len() - bn.bytes.len());
+    for b in padding {
+        *b = 0;
+    }
+    value.copy_from_slice(bn.bytes);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Name of the library.
+pub const BACKEND_NAME: &str = "ring";
+
+/// Returns version of the library, if known.
+///
+/// ring does not report its version at runtime.
+pub fn backend_version() -> Option<&'static str> {
+    None
+}
+
+/// Returns `true` if the library is running in FIPS mode.
+///
+/// ring is not FIPS-validated so this is always `false`.
+pub fn FIPS_mode() -> bool {
+    false
+}
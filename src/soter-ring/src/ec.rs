@@ -0,0 +1,159 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use zeroize::Zeroize;
+
+use crate::bn::BIGNUM;
+use crate::error::{Error, ErrorKind, Result};
+
+#[allow(non_upper_case_globals)]
+pub const NID_X9_62_prime256v1: c_int = 415;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum point_conversion_form_t {
+    /// Compressed point encoding: 0x02 or 0x03 byte followed by X coordinate.
+    POINT_CONVERSION_COMPRESSED = 2,
+    /// Uncompressed point encoding: 0x04 byte followed by X and Y coordinates.
+    POINT_CONVERSION_UNCOMPRESSED = 4,
+}
+
+const P256_SCALAR_SIZE: usize = 32;
+const P256_POINT_SIZE: usize = 1 + 2 * P256_SCALAR_SIZE;
+
+// ring exports generated keys only as PKCS #8 documents with fixed layout:
+//
+//     PKCS8_PREFIX || private key || PKCS8_MIDDLE || uncompressed public key
+//
+// See "ring/src/ec/suite_b/ecdsa/ecPublicKey_p256_pkcs8_v1_template.der".
+const PKCS8_PREFIX: &[u8] = &[
+    0x30, 0x81, 0x87, 0x02, 0x01, 0x00, 0x30, 0x13, 0x06, 0x07, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02,
+    0x01, 0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07, 0x04, 0x6D, 0x30, 0x6B, 0x02,
+    0x01, 0x01, 0x04, 0x20,
+];
+const PKCS8_MIDDLE: &[u8] = &[0xA1, 0x44, 0x03, 0x42, 0x00];
+
+#[allow(non_camel_case_types)]
+pub struct EC_KEY {
+    private_key: Option<[u8; P256_SCALAR_SIZE]>,
+    public_key: Option<[u8; P256_POINT_SIZE]>,
+}
+
+/// ring supports only one curve for our purposes so there is nothing to store here.
+#[allow(non_camel_case_types)]
+pub struct EC_GROUP<'a> {
+    _owner: PhantomData<&'a ()>,
+}
+
+#[allow(non_camel_case_types)]
+pub struct EC_POINT<'a> {
+    uncompressed: &'a [u8; P256_POINT_SIZE],
+}
+
+pub fn EC_KEY_new_by_curve_name(nid: c_int) -> Result<EC_KEY> {
+    if nid != NID_X9_62_prime256v1 {
+        return Err(Error::new(ErrorKind::NotSupported));
+    }
+    Ok(EC_KEY {
+        private_key: None,
+        public_key: None,
+    })
+}
+
+impl Drop for EC_KEY {
+    fn drop(&mut self) {
+        if let Some(private_key) = self.private_key.as_mut() {
+            private_key.zeroize();
+        }
+    }
+}
+
+pub fn EC_KEY_generate_key(key: &mut EC_KEY) -> Result<()> {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)?;
+    let document = pkcs8.as_ref();
+
+    let expected_size =
+        PKCS8_PREFIX.len() + P256_SCALAR_SIZE + PKCS8_MIDDLE.len() + P256_POINT_SIZE;
+    if document.len() != expected_size {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+    let (prefix, rest) = document.split_at(PKCS8_PREFIX.len());
+    let (private_key, rest) = rest.split_at(P256_SCALAR_SIZE);
+    let (middle, public_key) = rest.split_at(PKCS8_MIDDLE.len());
+    if prefix != PKCS8_PREFIX || middle != PKCS8_MIDDLE || public_key[0] != 0x04 {
+        return Err(Error::new(ErrorKind::Failure));
+    }
+
+    let mut new_private_key = [0; P256_SCALAR_SIZE];
+    new_private_key.copy_from_slice(private_key);
+    let mut new_public_key = [0; P256_POINT_SIZE];
+    new_public_key.copy_from_slice(public_key);
+
+    if let Some(old_private_key) = key.private_key.as_mut() {
+        old_private_key.zeroize();
+    }
+    key.private_key = Some(new_private_key);
+    key.public_key = Some(new_public_key);
+    Ok(())
+}
+
+pub fn EC_KEY_get0_group(_key: &EC_KEY) -> EC_GROUP<'_> {
+    EC_GROUP {
+        _owner: PhantomData,
+    }
+}
+
+pub fn EC_KEY_get0_private_key(key: &EC_KEY) -> Option<BIGNUM<'_>> {
+    key.private_key.as_ref().map(|k| BIGNUM::from_bytes(k))
+}
+
+pub fn EC_KEY_get0_public_key(key: &EC_KEY) -> Option<EC_POINT<'_>> {
+    key.public_key
+        .as_ref()
+        .map(|uncompressed| EC_POINT { uncompressed })
+}
+
+pub fn EC_POINT_point2oct<'a>(
+    _group: &EC_GROUP<'_>,
+    point: &EC_POINT<'_>,
+    form: point_conversion_form_t,
+    buffer: &'a mut [u8],
+) -> Result<&'a mut [u8]> {
+    let (x, y) = point.uncompressed[1..].split_at(P256_SCALAR_SIZE);
+    let need_size = match form {
+        point_conversion_form_t::POINT_CONVERSION_COMPRESSED => 1 + x.len(),
+        point_conversion_form_t::POINT_CONVERSION_UNCOMPRESSED => 1 + x.len() + y.len(),
+    };
+    if buffer.len() < need_size {
+        return Err(Error::new(ErrorKind::BufferTooSmall(need_size)));
+    }
+    let result = &mut buffer[..need_size];
+    match form {
+        point_conversion_form_t::POINT_CONVERSION_COMPRESSED => {
+            // Compressed form keeps only the parity of Y coordinate.
+            result[0] = 0x02 | (y[y.len() - 1] & 1);
+            result[1..].copy_from_slice(x);
+        }
+        point_conversion_form_t::POINT_CONVERSION_UNCOMPRESSED => {
+            result.copy_from_slice(point.uncompressed);
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,87 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::fmt;
+use std::result;
+
+/// Result of backend function calls.
+pub type Result<T> = result::Result<T, Error>;
+
+/// Error reported by ring.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+}
+
+/// List of ring error categories.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorKind {
+    /// General failure.
+    Failure,
+    /// Invalid parameter.
+    InvalidParameter,
+    /// Buffer is too small.
+    BufferTooSmall(usize),
+    /// Operation not supported.
+    NotSupported,
+}
+
+/// Error details are not reported by ring.
+///
+/// This type cannot be constructed, it exists for API compatibility with other backends.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ErrorDetail {}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Failure => write!(f, "failure"),
+            ErrorKind::InvalidParameter => write!(f, "invalid parameter"),
+            ErrorKind::BufferTooSmall(min) => write!(f, "buffer too small, need {} bytes", min),
+            ErrorKind::NotSupported => write!(f, "operation not supported"),
+        }
+    }
+}
+
+impl Error {
+    /// Creates a new error with given kind.
+    pub(crate) fn new(kind: ErrorKind) -> Error {
+        Error { kind }
+    }
+
+    /// Returns the corresponding `ErrorKind` for this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns errors reported by ring, which are always none.
+    pub fn details(&self) -> &[ErrorDetail] {
+        &[]
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+impl From<ring::error::Unspecified> for Error {
+    fn from(_: ring::error::Unspecified) -> Error {
+        Error::new(ErrorKind::Failure)
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ring::digest::{Context, SHA256, SHA512};
+
+use crate::error::{Error, ErrorKind, Result};
+
+#[allow(non_camel_case_types)]
+pub struct EVP_MD(&'static ring::digest::Algorithm);
+
+pub fn EVP_sha256() -> EVP_MD {
+    EVP_MD(&SHA256)
+}
+
+pub fn EVP_sha512() -> EVP_MD {
+    EVP_MD(&SHA512)
+}
+
+#[allow(non_camel_case_types)]
+pub struct EVP_MD_CTX(Option<Context>);
+
+pub fn EVP_MD_CTX_create() -> Result<EVP_MD_CTX> {
+    Ok(EVP_MD_CTX(None))
+}
+
+pub fn EVP_MD_CTX_size(ctx: &EVP_MD_CTX) -> usize {
+    match &ctx.0 {
+        Some(context) => context.algorithm().output_len,
+        None => 0,
+    }
+}
+
+pub fn EVP_DigestInit(ctx: &mut EVP_MD_CTX, type_: EVP_MD) -> Result<()> {
+    ctx.0 = Some(Context::new(type_.0));
+    Ok(())
+}
+
+pub fn EVP_DigestUpdate(ctx: &mut EVP_MD_CTX, bytes: &[u8]) -> Result<()> {
+    let context = ctx.0.as_mut().ok_or(Error::new(ErrorKind::Failure))?;
+    context.update(bytes);
+    Ok(())
+}
+
+pub fn EVP_DigestFinal_ex<'a>(ctx: &mut EVP_MD_CTX, buffer: &'a mut [u8]) -> Result<&'a [u8]> {
+    let need_size = EVP_MD_CTX_size(ctx);
+    if buffer.len() < need_size {
+        return Err(Error::new(ErrorKind::BufferTooSmall(need_size)));
+    }
+    // Like in BoringSSL, the context has to be initialised again after finalisation.
+    let context = ctx.0.take().ok_or(Error::new(ErrorKind::Failure))?;
+    let digest = context.finish();
+    let result = &mut buffer[..need_size];
+    result.copy_from_slice(digest.as_ref());
+    Ok(result)
+}
@@ -0,0 +1,50 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::bn::BIGNUM;
+use crate::error::{Error, ErrorKind, Result};
+
+/// RSA key.
+///
+/// ring cannot generate RSA keys so this one is always empty.
+pub struct RSA(());
+
+/// Allocates a new empty RSA key.
+pub fn RSA_new() -> Result<RSA> {
+    Ok(RSA(()))
+}
+
+/// Generates a new RSA key with a modulus of given size and given public exponent.
+///
+/// ring does not support RSA key generation, this always fails with `NotSupported`.
+pub fn RSA_generate_key_ex(_rsa: &mut RSA, _bits: usize, _e: u32) -> Result<()> {
+    Err(Error::new(ErrorKind::NotSupported))
+}
+
+/// Returns modulus, public exponent, and private exponent of the key, if they are set.
+pub fn RSA_get0_key(_rsa: &RSA) -> (Option<BIGNUM<'_>>, Option<BIGNUM<'_>>, Option<BIGNUM<'_>>) {
+    (None, None, None)
+}
+
+/// Returns prime factors of the modulus, if they are set.
+pub fn RSA_get0_factors(_rsa: &RSA) -> (Option<BIGNUM<'_>>, Option<BIGNUM<'_>>) {
+    (None, None)
+}
+
+/// Returns CRT parameters (dmp1, dmq1, iqmp) of the key, if they are set.
+pub fn RSA_get0_crt_params(
+    _rsa: &RSA,
+) -> (Option<BIGNUM<'_>>, Option<BIGNUM<'_>>, Option<BIGNUM<'_>>) {
+    (None, None, None)
+}
@@ -0,0 +1,232 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Soter data containers.
+//!
+//! Containers wrap binary data (most notably, keys) with a type tag and a checksum.
+//! The layout is compatible with containers used by C Themis:
+//!
+//! ```text
+//! +----------+--------------+----------+------------------+
+//! | tag: 4 B | size: u32 BE | crc: 4 B | data: size - 12  |
+//! +----------+--------------+----------+------------------+
+//! ```
+//!
+//! The size includes the header. The checksum is CRC-32C of the entire container,
+//! computed with the checksum field filled with zeros.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::crc::CRC32C;
+use crate::error::{Error, ErrorKind, Result};
+
+/// Size of container tag in bytes.
+pub const TAG_SIZE: usize = 4;
+
+/// Size of container header in bytes.
+pub const HEADER_SIZE: usize = 12;
+
+/// Soter data container.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> soter::Result<()> {
+/// use soter::container::Container;
+///
+/// let encoded = Container::new(*b"TEST", b"data").to_vec()?;
+///
+/// let container = Container::parse(&encoded)?;
+/// assert_eq!(container.tag(), b"TEST");
+/// assert_eq!(container.data(), b"data");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Container<'a> {
+    tag: [u8; TAG_SIZE],
+    data: &'a [u8],
+}
+
+impl<'a> Container<'a> {
+    /// Makes a new container with given tag and data.
+    pub fn new(tag: [u8; TAG_SIZE], data: &'a [u8]) -> Container<'a> {
+        Container { tag, data }
+    }
+
+    /// Parses an encoded container.
+    ///
+    /// The entire input must be occupied by exactly one container.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`MalformedData`] kind if the input is not a valid container:
+    /// it is too short, its size does not match, or the checksum is incorrect.
+    ///
+    /// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+    pub fn parse(bytes: &'a [u8]) -> Result<Container<'a>> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(Error::new(ErrorKind::MalformedData));
+        }
+        let (header, data) = bytes.split_at(HEADER_SIZE);
+        let mut tag = [0; TAG_SIZE];
+        let mut size = [0; 4];
+        let mut crc = [0; 4];
+        tag.copy_from_slice(&header[0..4]);
+        size.copy_from_slice(&header[4..8]);
+        crc.copy_from_slice(&header[8..12]);
+        if u32::from_be_bytes(size) as usize != bytes.len() {
+            return Err(Error::new(ErrorKind::MalformedData));
+        }
+        if checksum(&tag, size, data) != crc {
+            return Err(Error::new(ErrorKind::MalformedData));
+        }
+        Ok(Container { tag, data })
+    }
+
+    /// Returns the tag of this container.
+    pub fn tag(&self) -> &[u8; TAG_SIZE] {
+        &self.tag
+    }
+
+    /// Returns the data stored in this container.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the size of this container when encoded, in bytes.
+    pub fn encoded_size(&self) -> usize {
+        HEADER_SIZE + self.data.len()
+    }
+
+    /// Encodes this container into the provided buffer.
+    ///
+    /// The container is written from the beginning of the buffer and a slice of the buffer
+    /// with the encoded container is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is too small for the result to fit, an error of [`BufferTooSmall`] kind
+    /// is returned, indicating the minimum size needed.
+    ///
+    /// Containers cannot be larger than 4 GB. An error of [`InvalidParameter`] kind
+    /// is returned if the data is too big.
+    ///
+    /// [`BufferTooSmall`]: ../enum.ErrorKind.html#variant.BufferTooSmall
+    /// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+    pub fn encode<'b>(&self, buffer: &'b mut [u8]) -> Result<&'b [u8]> {
+        let total_size = self.encoded_size();
+        if total_size > u32::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        if buffer.len() < total_size {
+            return Err(Error::new(ErrorKind::BufferTooSmall(total_size)));
+        }
+        let size = (total_size as u32).to_be_bytes();
+        let crc = checksum(&self.tag, size, self.data);
+        let buffer = &mut buffer[..total_size];
+        buffer[0..4].copy_from_slice(&self.tag);
+        buffer[4..8].copy_from_slice(&size);
+        buffer[8..12].copy_from_slice(&crc);
+        buffer[HEADER_SIZE..].copy_from_slice(self.data);
+        Ok(buffer)
+    }
+
+    /// Encodes this container into a newly allocated vector.
+    ///
+    /// This is a convenience wrapper over [`encode`].
+    ///
+    /// [`encode`]: struct.Container.html#method.encode
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        let mut buffer = vec![0; self.encoded_size()];
+        self.encode(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+fn checksum(tag: &[u8; TAG_SIZE], size: [u8; 4], data: &[u8]) -> [u8; 4] {
+    let mut crc = CRC32C::new();
+    crc.update(tag);
+    crc.update(size);
+    crc.update([0; 4]);
+    crc.update(data);
+    // C Themis stores the checksum in network byte order,
+    // as it is returned by our CRC32C (with swapped bytes).
+    crc.complete().to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hex_literal::hex;
+
+    // EC private key generated by C Themis.
+    const THEMIS_KEY: [u8; 45] = hex!("52454332 0000002d 773c2559 00a39ccd95d4a6f921b02fb1bf05a7bf708b9a51b45b3cbc6413274fedb2c7f760");
+
+    #[test]
+    fn parse_themis_container() {
+        let container = Container::parse(&THEMIS_KEY).expect("valid container");
+        assert_eq!(container.tag(), b"REC2");
+        assert_eq!(container.data(), &THEMIS_KEY[12..]);
+    }
+
+    #[test]
+    fn encode_themis_container() {
+        let mut tag = [0; TAG_SIZE];
+        tag.copy_from_slice(&THEMIS_KEY[0..4]);
+        let container = Container::new(tag, &THEMIS_KEY[12..]);
+        assert_eq!(container.to_vec().unwrap(), &THEMIS_KEY[..]);
+    }
+
+    #[test]
+    fn empty_data() {
+        let encoded = Container::new(*b"NONE", &[]).to_vec().unwrap();
+        assert_eq!(encoded.len(), HEADER_SIZE);
+        let container = Container::parse(&encoded).unwrap();
+        assert_eq!(container.data(), &[]);
+    }
+
+    #[test]
+    fn corrupted_data() {
+        for i in 0..THEMIS_KEY.len() {
+            let mut corrupted = THEMIS_KEY;
+            corrupted[i] ^= 0x10;
+            let err = Container::parse(&corrupted).expect_err("corrupted container");
+            assert_eq!(err.kind(), ErrorKind::MalformedData);
+        }
+    }
+
+    #[test]
+    fn wrong_length() {
+        let malformed = |bytes| {
+            let err = Container::parse(bytes).expect_err("wrong length");
+            err.kind() == ErrorKind::MalformedData
+        };
+        assert!(malformed(&THEMIS_KEY[..HEADER_SIZE - 1]));
+        assert!(malformed(&THEMIS_KEY[..THEMIS_KEY.len() - 1]));
+        let mut extended = THEMIS_KEY.to_vec();
+        extended.push(0);
+        assert!(malformed(&extended));
+    }
+
+    #[test]
+    fn encode_short_buffer() {
+        let container = Container::new(*b"TEST", b"data");
+        let mut buffer = [0; 8];
+        let err = container.encode(&mut buffer).expect_err("short buffer");
+        assert_eq!(err.kind(), ErrorKind::BufferTooSmall(16));
+    }
+}
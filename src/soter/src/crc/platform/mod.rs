@@ -14,12 +14,14 @@
 
 //! Platform-specific implementations of CRC.
 
-use std::mem::transmute;
-use std::sync::atomic::{AtomicPtr, Ordering};
+use core::mem::transmute;
+use core::sync::atomic::{AtomicPtr, Ordering};
 
 pub mod software;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod sse42;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub mod vpclmulqdq;
 
 /// Updates CRC-32C state in the most efficient way for the platform.
 ///
@@ -28,7 +30,12 @@ pub fn update_crc32c_runtime(state: u32, data: &[u8]) -> u32 {
     // x86 processors with SSE 4.2 instruction set can compute CRC-32C much faster.
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     unsafe {
-        if is_x86_feature_detected!("sse4.2") {
+        // AVX-512 capable processors can go even faster by folding with carry-less multiply.
+        if has_vpclmulqdq() {
+            // We have checked for AVX-512 availability, it is safe to proceed.
+            return vpclmulqdq::update_crc32c(state, data);
+        }
+        if x86_feature_detected!("sse4.2") {
             // We have checked for SSE 4.2 availability, it is safe to proceed.
             return sse42::update_crc32c(state, data);
         }
@@ -74,7 +81,10 @@ pub fn update_crc32c_lazy(state: u32, data: &[u8]) -> u32 {
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 #[allow(clippy::crosspointer_transmute)]
 fn detect_update_crc32c(state: u32, data: &[u8]) -> u32 {
-    let crc = if is_x86_feature_detected!("sse4.2") {
+    let crc = if has_vpclmulqdq() {
+        // We have checked for AVX-512 availability, it is safe to lift the "unsafe" marker.
+        unsafe { transmute::<UnsafeFnCRC32, FnCRC32>(vpclmulqdq::update_crc32c) }
+    } else if x86_feature_detected!("sse4.2") {
         // We have checked for SSE 4.2 availability, it is safe to lift the "unsafe" marker.
         unsafe { transmute::<UnsafeFnCRC32, FnCRC32>(sse42::update_crc32c) }
     } else {
@@ -83,3 +93,8 @@ fn detect_update_crc32c(state: u32, data: &[u8]) -> u32 {
     UPDATE_CRC32C.store(crc as *mut FnCRC32, Ordering::Relaxed);
     crc(state, data)
 }
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn has_vpclmulqdq() -> bool {
+    x86_feature_detected!("avx512f", "vpclmulqdq", "sse4.2")
+}
@@ -51,9 +51,9 @@ pub unsafe fn update_crc32c(state: u32, data: &[u8]) -> u32 {
 #[target_feature(enable = "sse4.2")]
 pub unsafe fn update_crc32c_linear(mut state: u32, data: &[u8]) -> u32 {
     #[cfg(target_arch = "x86")]
-    use std::arch::x86::_mm_crc32_u8;
+    use core::arch::x86::_mm_crc32_u8;
     #[cfg(target_arch = "x86_64")]
-    use std::arch::x86_64::_mm_crc32_u8;
+    use core::arch::x86_64::_mm_crc32_u8;
     for byte in data {
         state = _mm_crc32_u8(state, *byte);
     }
@@ -73,7 +73,7 @@ pub unsafe fn update_crc32c_linear(mut state: u32, data: &[u8]) -> u32 {
 #[target_feature(enable = "sse4.2")]
 #[cfg(target_arch = "x86")]
 pub unsafe fn update_crc32c_unrolled(mut state: u32, data: &[u8]) -> u32 {
-    use std::arch::x86::{_mm_crc32_u32, _mm_crc32_u8};
+    use core::arch::x86::{_mm_crc32_u32, _mm_crc32_u8};
     let (prefix, dwords, suffix) = data.align_to();
     for byte in prefix {
         state = _mm_crc32_u8(state, *byte);
@@ -100,7 +100,7 @@ pub unsafe fn update_crc32c_unrolled(mut state: u32, data: &[u8]) -> u32 {
 #[target_feature(enable = "sse4.2")]
 #[cfg(target_arch = "x86_64")]
 pub unsafe fn update_crc32c_unrolled(mut state: u32, data: &[u8]) -> u32 {
-    use std::arch::x86_64::{_mm_crc32_u64, _mm_crc32_u8};
+    use core::arch::x86_64::{_mm_crc32_u64, _mm_crc32_u8};
     let (prefix, qwords, suffix) = data.align_to();
     for byte in prefix {
         state = _mm_crc32_u8(state, *byte);
@@ -116,7 +116,7 @@ pub unsafe fn update_crc32c_unrolled(mut state: u32, data: &[u8]) -> u32 {
     state
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     mod crc32c {
         use crate::crc::platform::{software, sse42};
@@ -0,0 +1,225 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! AVX-512 VPCLMULQDQ implementations of CRC.
+//!
+//! All functions here are **unsafe**.
+//! Ensure instruction set availability with `is_x86_feature_detected!` before calling them:
+//! "avx512f", "vpclmulqdq", and "sse4.2" are required.
+//!
+//! The data is folded with carry-less multiplication, 64 bytes per instruction,
+//! and the folded remainder is reduced with `crc32` instruction from SSE 4.2.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::sse42;
+
+/// Threshold for using folding CRC-32C computation.
+///
+/// [`update_crc32c`](fn.update_crc32c.html) falls back to
+/// [`sse42::update_crc32c`](../sse42/fn.update_crc32c.html) on data buffers shorter than this.
+pub const CRC32C_FOLD_THRESHOLD: usize = 4 * BLOCK;
+
+/// Size of ZMM register.
+const BLOCK: usize = 64;
+
+/// CRC-32C polynomial in normal representation, without the leading x^32 term.
+const CRC32C_POLY: u32 = 0x1EDC_6F41;
+
+/// Computes x^n mod P(x), bit-reflected and positioned for `vpclmulqdq`.
+///
+/// Reflected carry-less products are off by one bit, so the constant is x^(n-1) instead.
+const fn fold_key(n: u32) -> i64 {
+    let mut value: u32 = 1;
+    let mut i = 1;
+    while i < n {
+        let carry = value & 0x8000_0000 != 0;
+        value <<= 1;
+        if carry {
+            value ^= CRC32C_POLY;
+        }
+        i += 1;
+    }
+    ((value.reverse_bits() as u64) << 32) as i64
+}
+
+/// Constants for folding a 128-bit lane forward by `bits`.
+///
+/// Returns keys for the low and high quadwords of a lane. The low quadword holds
+/// high-order polynomial coefficients so it is folded 64 bits further.
+const fn fold_keys(bits: u32) -> (i64, i64) {
+    (fold_key(bits + 64), fold_key(bits))
+}
+
+const FOLD_BY_4_BLOCKS: (i64, i64) = fold_keys(4 * BLOCK as u32 * 8);
+const FOLD_BY_1_BLOCK: (i64, i64) = fold_keys(BLOCK as u32 * 8);
+const FOLD_BY_384: (i64, i64) = fold_keys(384);
+const FOLD_BY_256: (i64, i64) = fold_keys(256);
+const FOLD_BY_128: (i64, i64) = fold_keys(128);
+
+/// Updates CRC-32C state using `vpclmulqdq` instruction.
+///
+/// This is up to 10 times faster than [`sse42::update_crc32c`](../sse42/fn.update_crc32c.html)
+/// on buffers of several kilobytes, given sufficient runway.
+///
+/// # Safety
+///
+/// This function uses AVX-512F, VPCLMULQDQ, and SSE 4.2 instructions.
+/// Make sure the CPU supports them before calling this function.
+/// Otherwise the process will typically be killed by the operating system.
+#[target_feature(enable = "avx512f,vpclmulqdq,sse4.2")]
+pub unsafe fn update_crc32c(state: u32, data: &[u8]) -> u32 {
+    if data.len() < CRC32C_FOLD_THRESHOLD {
+        return sse42::update_crc32c(state, data);
+    }
+
+    let mut chunks = data.chunks_exact(4 * BLOCK);
+    let first = chunks.next().expect("at least one chunk");
+
+    // CRC state is equivalent to XOR-ing it with the first bytes of data and starting from zero.
+    let state = _mm512_zextsi128_si512(_mm_cvtsi32_si128(state as i32));
+    let mut x0 = _mm512_xor_si512(load(first, 0), state);
+    let mut x1 = load(first, 1);
+    let mut x2 = load(first, 2);
+    let mut x3 = load(first, 3);
+
+    let k = keys(FOLD_BY_4_BLOCKS);
+    for chunk in &mut chunks {
+        x0 = fold(x0, k, load(chunk, 0));
+        x1 = fold(x1, k, load(chunk, 1));
+        x2 = fold(x2, k, load(chunk, 2));
+        x3 = fold(x3, k, load(chunk, 3));
+    }
+
+    let k = keys(FOLD_BY_1_BLOCK);
+    let mut x = fold(x0, k, x1);
+    x = fold(x, k, x2);
+    x = fold(x, k, x3);
+
+    let mut blocks = chunks.remainder().chunks_exact(BLOCK);
+    for block in &mut blocks {
+        x = fold(x, k, load(block, 0));
+    }
+
+    // Fold the lanes into the last one, then let the crc32 instruction reduce it.
+    let k = _mm512_set_epi64(
+        0,
+        0,
+        FOLD_BY_128.1,
+        FOLD_BY_128.0,
+        FOLD_BY_256.1,
+        FOLD_BY_256.0,
+        FOLD_BY_384.1,
+        FOLD_BY_384.0,
+    );
+    let folded = _mm512_xor_si512(
+        _mm512_clmulepi64_epi128(x, k, 0x00),
+        _mm512_clmulepi64_epi128(x, k, 0x11),
+    );
+    let last = _mm_xor_si128(
+        _mm_xor_si128(
+            _mm512_extracti32x4_epi32(folded, 0),
+            _mm512_extracti32x4_epi32(folded, 1),
+        ),
+        _mm_xor_si128(
+            _mm512_extracti32x4_epi32(folded, 2),
+            _mm512_extracti32x4_epi32(x, 3),
+        ),
+    );
+    let mut remainder = [0; 16];
+    _mm_storeu_si128(remainder.as_mut_ptr().cast(), last);
+
+    let state = sse42::update_crc32c(0, &remainder);
+    sse42::update_crc32c(state, blocks.remainder())
+}
+
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn load(chunk: &[u8], index: usize) -> __m512i {
+    let block = &chunk[index * BLOCK..(index + 1) * BLOCK];
+    _mm512_loadu_si512(block.as_ptr().cast())
+}
+
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn keys((lo, hi): (i64, i64)) -> __m512i {
+    _mm512_set_epi64(hi, lo, hi, lo, hi, lo, hi, lo)
+}
+
+/// Folds 128-bit lanes of `x` forward onto `data`.
+#[inline]
+#[target_feature(enable = "avx512f,vpclmulqdq")]
+unsafe fn fold(x: __m512i, k: __m512i, data: __m512i) -> __m512i {
+    // 0x96 is three-way XOR.
+    _mm512_ternarylogic_epi64(
+        _mm512_clmulepi64_epi128(x, k, 0x00),
+        _mm512_clmulepi64_epi128(x, k, 0x11),
+        data,
+        0x96,
+    )
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    mod crc32c {
+        use crate::crc::platform::{software, vpclmulqdq};
+        use crate::crc::INIT_CRC32;
+        use crate::rand;
+
+        fn supported() -> bool {
+            is_x86_feature_detected!("avx512f")
+                && is_x86_feature_detected!("vpclmulqdq")
+                && is_x86_feature_detected!("sse4.2")
+        }
+
+        // Make sure that optimized behavior is identical to software implementation.
+        // Cover all combinations of full chunks, remaining blocks, and tail bytes.
+        #[test]
+        fn same_as_software() {
+            if !supported() {
+                return;
+            }
+            let mut input = [0; 1024 + 64];
+            rand::bytes(&mut input);
+            for length in 0..=input.len() {
+                unsafe {
+                    let input = &input[0..length];
+                    let software = software::update_crc32c(INIT_CRC32, input);
+                    let vpclmulqdq = vpclmulqdq::update_crc32c(INIT_CRC32, input);
+                    assert_eq!(vpclmulqdq, software, "length: {}", length);
+                }
+            }
+        }
+
+        #[test]
+        fn unaligned_input() {
+            if !supported() {
+                return;
+            }
+            let mut input = [0; 4096];
+            rand::bytes(&mut input);
+            for offset in 0..64 {
+                unsafe {
+                    let input = &input[offset..];
+                    let software = software::update_crc32c(0x12345678, input);
+                    let vpclmulqdq = vpclmulqdq::update_crc32c(0x12345678, input);
+                    assert_eq!(vpclmulqdq, software, "offset: {}", offset);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Elliptic curve cryptography.
+
+use std::fmt;
+
+use crate::backend::{
+    point_conversion_form_t, BN_bn2bin_padded, EC_KEY_generate_key, EC_KEY_get0_group,
+    EC_KEY_get0_private_key, EC_KEY_get0_public_key, EC_KEY_new_by_curve_name, EC_POINT_point2oct,
+    NID_X9_62_prime256v1,
+};
+use zeroize::Zeroize;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::memory::Redacted;
+
+/// Elliptic curves supported by Soter.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Curve {
+    /// NIST P-256 curve, also known as secp256r1 and prime256v1.
+    P256,
+}
+
+impl Curve {
+    /// Returns the size of curve field elements and scalars in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            Curve::P256 => 32,
+        }
+    }
+}
+
+/// Elliptic curve key pair.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> soter::Result<()> {
+/// use soter::ec::{Curve, KeyPair};
+///
+/// let key_pair = KeyPair::generate(Curve::P256)?;
+///
+/// assert_eq!(key_pair.private_key().len(), 32);
+/// assert_eq!(key_pair.public_key().len(), 33);
+/// # Ok(())
+/// # }
+/// ```
+pub struct KeyPair {
+    curve: Curve,
+    private_key: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+impl KeyPair {
+    /// Generates a new random key pair on the given curve.
+    pub fn generate(curve: Curve) -> Result<KeyPair> {
+        let nid = match curve {
+            Curve::P256 => NID_X9_62_prime256v1,
+        };
+        let mut key = EC_KEY_new_by_curve_name(nid)?;
+        EC_KEY_generate_key(&mut key)?;
+
+        // Key pair wipes the private key on drop, including early returns on errors.
+        let mut key_pair = KeyPair {
+            curve,
+            private_key: vec![0; curve.size()],
+            public_key: vec![0; 1 + curve.size()],
+        };
+
+        let scalar = EC_KEY_get0_private_key(&key).ok_or(Error::new(ErrorKind::Failure))?;
+        BN_bn2bin_padded(&mut key_pair.private_key, &scalar)?;
+
+        let group = EC_KEY_get0_group(&key);
+        let point = EC_KEY_get0_public_key(&key).ok_or(Error::new(ErrorKind::Failure))?;
+        let form = point_conversion_form_t::POINT_CONVERSION_COMPRESSED;
+        EC_POINT_point2oct(&group, &point, form, &mut key_pair.public_key)?;
+
+        Ok(key_pair)
+    }
+
+    /// Returns the curve of this key pair.
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Returns the private key.
+    ///
+    /// Private key is a big-endian scalar, padded with zeros to the curve size.
+    pub fn private_key(&self) -> &[u8] {
+        &self.private_key
+    }
+
+    /// Returns the public key.
+    ///
+    /// Public key is a compressed curve point.
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+}
+
+impl Drop for KeyPair {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+// Never print out the private key.
+impl fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("curve", &self.curve)
+            .field("private_key", &Redacted::new(&self.private_key))
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_p256() {
+        let key_pair = KeyPair::generate(Curve::P256).expect("key generation");
+        assert_eq!(key_pair.curve(), Curve::P256);
+        assert_eq!(key_pair.private_key().len(), 32);
+        assert_eq!(key_pair.public_key().len(), 33);
+        assert!(key_pair.public_key()[0] == 0x02 || key_pair.public_key()[0] == 0x03);
+    }
+
+    #[test]
+    fn keys_are_random() {
+        let key_pair_1 = KeyPair::generate(Curve::P256).unwrap();
+        let key_pair_2 = KeyPair::generate(Curve::P256).unwrap();
+        assert_ne!(key_pair_1.private_key(), key_pair_2.private_key());
+        assert_ne!(key_pair_1.public_key(), key_pair_2.public_key());
+    }
+
+    #[test]
+    fn private_key_is_not_printed() {
+        let key_pair = KeyPair::generate(Curve::P256).expect("key generation");
+        let debug = format!("{:?}", key_pair);
+        assert!(debug.contains("private_key: <redacted 32 bytes>"));
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Constant-time encodings.
+//!
+//! Keys and other secrets are often encoded as text. Usual implementations of hex and
+//! Base64 use lookup tables and branches which depend on the data, leaking it through
+//! cache and timing side channels. Functions in this module take time which depends
+//! only on the length of the data.
+//!
+//! Base64 uses the standard alphabet (RFC 4648, section 4) with padding.
+//! Hex encoding uses lowercase digits, decoding accepts both cases.
+//!
+//! Base64 uses SSSE3 instructions on x86 processors which support them, processing
+//! 12 bytes at a time. This code is branch-free too.
+//!
+//! Decoding is strict: whitespace, non-canonical trailing bits, and misplaced padding
+//! are rejected. Unpadded Base64 is accepted for compatibility.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> soter::Result<()> {
+//! use soter::encoding;
+//!
+//! assert_eq!(encoding::base64_encode(b"secret"), "c2VjcmV0");
+//! assert_eq!(encoding::base64_decode("c2VjcmV0")?, b"secret");
+//!
+//! assert_eq!(encoding::hex_encode(b"secret"), "736563726574");
+//! assert_eq!(encoding::hex_decode("736563726574")?, b"secret");
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use zeroize::Zeroize;
+
+use crate::error::{Error, ErrorKind, Result};
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod ssse3;
+
+/// Encodes data in Base64, with padding.
+pub fn base64_encode(data: impl AsRef<[u8]>) -> String {
+    let data = data.as_ref();
+    let mut encoded = Vec::with_capacity(data.len().div_ceil(3) * 4);
+    let consumed = base64_encode_simd(data, &mut encoded);
+    base64_encode_scalar(&data[consumed..], &mut encoded);
+    String::from_utf8(encoded).expect("Base64 is ASCII")
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn base64_encode_simd(data: &[u8], encoded: &mut Vec<u8>) -> usize {
+    if x86_feature_detected!("ssse3") {
+        // We have checked for SSSE3 availability, it is safe to proceed.
+        unsafe { ssse3::base64_encode(data, encoded) }
+    } else {
+        0
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn base64_encode_simd(_data: &[u8], _encoded: &mut Vec<u8>) -> usize {
+    0
+}
+
+fn base64_encode_scalar(data: &[u8], encoded: &mut Vec<u8>) {
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        encoded.push(base64_char(b0 >> 2));
+        encoded.push(base64_char(((b0 << 4) | (b1 >> 4)) & 0x3F));
+        if chunk.len() > 1 {
+            encoded.push(base64_char(((b1 << 2) | (b2 >> 6)) & 0x3F));
+        } else {
+            encoded.push(b'=');
+        }
+        if chunk.len() > 2 {
+            encoded.push(base64_char(b2 & 0x3F));
+        } else {
+            encoded.push(b'=');
+        }
+    }
+}
+
+/// Decodes Base64 data.
+///
+/// # Errors
+///
+/// Returns an error of [`MalformedData`] kind if the input is not valid Base64.
+///
+/// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+pub fn base64_decode(encoded: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+    let mut encoded = encoded.as_ref();
+    // Padding position depends only on the length, it is not secret.
+    if encoded.len() % 4 == 0 && encoded.ends_with(b"=") {
+        encoded = &encoded[..encoded.len() - 1];
+        if encoded.ends_with(b"=") {
+            encoded = &encoded[..encoded.len() - 1];
+        }
+    }
+    if encoded.len() % 4 == 1 {
+        return Err(Error::new(ErrorKind::MalformedData));
+    }
+
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    let (consumed, valid) = base64_decode_simd(encoded, &mut decoded);
+    let mut invalid = -(!valid as i16);
+    for chunk in encoded[consumed..].chunks(4) {
+        let mut bits: u32 = 0;
+        for &c in chunk {
+            let value = base64_value(c);
+            invalid |= value >> 8;
+            bits = (bits << 6) | (value as u32 & 0x3F);
+        }
+        match chunk.len() {
+            4 => {
+                decoded.push((bits >> 16) as u8);
+                decoded.push((bits >> 8) as u8);
+                decoded.push(bits as u8);
+            }
+            3 => {
+                decoded.push((bits >> 10) as u8);
+                decoded.push((bits >> 2) as u8);
+                invalid |= (bits & 0x03) as i16;
+            }
+            2 => {
+                decoded.push((bits >> 4) as u8);
+                invalid |= (bits & 0x0F) as i16;
+            }
+            _ => unreachable!(),
+        }
+    }
+    if invalid != 0 {
+        decoded.zeroize();
+        return Err(Error::new(ErrorKind::MalformedData));
+    }
+    Ok(decoded)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn base64_decode_simd(encoded: &[u8], decoded: &mut Vec<u8>) -> (usize, bool) {
+    if x86_feature_detected!("ssse3") {
+        // We have checked for SSSE3 availability, it is safe to proceed.
+        unsafe { ssse3::base64_decode(encoded, decoded) }
+    } else {
+        (0, true)
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn base64_decode_simd(_encoded: &[u8], _decoded: &mut Vec<u8>) -> (usize, bool) {
+    (0, true)
+}
+
+/// Encodes data in lowercase hex.
+pub fn hex_encode(data: impl AsRef<[u8]>) -> String {
+    let data = data.as_ref();
+    let mut encoded = Vec::with_capacity(2 * data.len());
+    for &byte in data {
+        encoded.push(hex_char(byte >> 4));
+        encoded.push(hex_char(byte & 0x0F));
+    }
+    String::from_utf8(encoded).expect("hex is ASCII")
+}
+
+/// Decodes hex data.
+///
+/// Both lowercase and uppercase digits are accepted.
+///
+/// # Errors
+///
+/// Returns an error of [`MalformedData`] kind if the input is not valid hex.
+///
+/// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+pub fn hex_decode(encoded: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+    let encoded = encoded.as_ref();
+    if encoded.len() % 2 != 0 {
+        return Err(Error::new(ErrorKind::MalformedData));
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() / 2);
+    let mut invalid = 0;
+    for pair in encoded.chunks(2) {
+        let high = hex_value(pair[0]);
+        let low = hex_value(pair[1]);
+        invalid |= (high | low) >> 8;
+        decoded.push(((high << 4) | (low & 0x0F)) as u8);
+    }
+    if invalid != 0 {
+        decoded.zeroize();
+        return Err(Error::new(ErrorKind::MalformedData));
+    }
+    Ok(decoded)
+}
+
+// The following functions avoid branches and table lookups. Range checks are computed
+// with arithmetic: (lower - c) & (c - upper) is negative only if lower < c < upper,
+// and the arithmetic right shift turns it into an all-ones mask.
+
+/// Returns Base64 character for a 6-bit value.
+fn base64_char(value: u8) -> u8 {
+    let x = value as i16;
+    let mut c = x + b'A' as i16;
+    c += ((25 - x) >> 8) & 6; // 26..=51 => 'a'..='z'
+    c -= ((51 - x) >> 8) & 75; // 52..=61 => '0'..='9'
+    c -= ((61 - x) >> 8) & 15; // 62 => '+'
+    c += ((62 - x) >> 8) & 3; // 63 => '/'
+    c as u8
+}
+
+/// Returns 6-bit value of a Base64 character, or -1 if the character is invalid.
+fn base64_value(c: u8) -> i16 {
+    let c = c as i16;
+    let mut value = -1;
+    value += (((0x40 - c) & (c - 0x5B)) >> 8) & (c - 64); // 'A'..='Z'
+    value += (((0x60 - c) & (c - 0x7B)) >> 8) & (c - 70); // 'a'..='z'
+    value += (((0x2F - c) & (c - 0x3A)) >> 8) & (c + 5); // '0'..='9'
+    value += (((0x2A - c) & (c - 0x2C)) >> 8) & 63; // '+'
+    value += (((0x2E - c) & (c - 0x30)) >> 8) & 64; // '/'
+    value
+}
+
+/// Returns lowercase hex digit for a 4-bit value.
+fn hex_char(value: u8) -> u8 {
+    let x = value as i16;
+    let c = x + b'0' as i16 + (((9 - x) >> 8) & 39); // 10..=15 => 'a'..='f'
+    c as u8
+}
+
+/// Returns 4-bit value of a hex digit, or -1 if the digit is invalid.
+fn hex_value(c: u8) -> i16 {
+    let c = c as i16;
+    let mut value = -1;
+    value += (((0x2F - c) & (c - 0x3A)) >> 8) & (c - 47); // '0'..='9'
+    value += (((0x60 - c) & (c - 0x67)) >> 8) & (c - 86); // 'a'..='f'
+    value += (((0x40 - c) & (c - 0x47)) >> 8) & (c - 54); // 'A'..='F'
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4648, section 10
+    const VECTORS: &[(&[u8], &str, &str)] = &[
+        (b"", "", ""),
+        (b"f", "Zg==", "66"),
+        (b"fo", "Zm8=", "666f"),
+        (b"foo", "Zm9v", "666f6f"),
+        (b"foob", "Zm9vYg==", "666f6f62"),
+        (b"fooba", "Zm9vYmE=", "666f6f6261"),
+        (b"foobar", "Zm9vYmFy", "666f6f626172"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, base64, hex) in VECTORS {
+            assert_eq!(base64_encode(data), base64);
+            assert_eq!(base64_decode(base64).unwrap(), data);
+            assert_eq!(hex_encode(data), hex);
+            assert_eq!(hex_decode(hex).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn all_values() {
+        let data: Vec<u8> = (0..=255).collect();
+        let hex = hex_encode(&data);
+        assert_eq!(hex_decode(&hex).unwrap(), data);
+        assert_eq!(hex_decode(hex.to_uppercase()).unwrap(), data);
+
+        let base64 = base64_encode(&data);
+        assert!(base64.starts_with("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8g"));
+        assert!(base64.ends_with("+fr7/P3+/w=="));
+        assert_eq!(base64_decode(&base64).unwrap(), data);
+    }
+
+    #[test]
+    fn alphabet() {
+        let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        for c in 0..=255u8 {
+            let expected = alphabet.iter().position(|&a| a == c);
+            let value = base64_value(c);
+            match expected {
+                Some(index) => assert_eq!(value, index as i16),
+                None => assert_eq!(value, -1, "{:?}", c as char),
+            }
+        }
+        for (index, &c) in alphabet.iter().enumerate() {
+            assert_eq!(base64_char(index as u8), c);
+        }
+    }
+
+    #[test]
+    fn unpadded_base64() {
+        assert_eq!(base64_decode("Zg").unwrap(), b"f");
+        assert_eq!(base64_decode("Zm8").unwrap(), b"fo");
+    }
+
+    #[test]
+    fn invalid_base64() {
+        for &encoded in &[
+            "Z", "Zg=", "Zg===", "Z===", "=", "Zh==", "Zm9=", "Zm 9v", "Zm9v\n", "Zm=v", "Zm9-",
+        ] {
+            let err = base64_decode(encoded).expect_err(encoded);
+            assert_eq!(err.kind(), ErrorKind::MalformedData);
+        }
+    }
+
+    #[test]
+    fn long_base64() {
+        // Long inputs exercise the vectorized code, make sure it agrees with scalar code.
+        let data: Vec<u8> = (0..256).map(|i| (i * 167 + 13) as u8).collect();
+        for length in 0..=data.len() {
+            let data = &data[..length];
+            let mut expected = Vec::new();
+            base64_encode_scalar(data, &mut expected);
+            let encoded = base64_encode(data);
+            assert_eq!(encoded.as_bytes(), &expected[..], "length: {}", length);
+            assert_eq!(base64_decode(&encoded).unwrap(), data, "length: {}", length);
+        }
+    }
+
+    #[test]
+    fn invalid_long_base64() {
+        let encoded = base64_encode([0xA5; 48]).into_bytes();
+        for position in 0..encoded.len() {
+            for &c in b" \n=-_.\x80\xFF" {
+                let mut corrupted = encoded.clone();
+                corrupted[position] = c;
+                if let Ok(decoded) = base64_decode(&corrupted) {
+                    panic!("{:?} at {}: {:?}", c as char, position, decoded);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_hex() {
+        for &encoded in &["6", "666", "6g", "g6", " 66", "0x66"] {
+            let err = hex_decode(encoded).expect_err(encoded);
+            assert_eq!(err.kind(), ErrorKind::MalformedData);
+        }
+    }
+}
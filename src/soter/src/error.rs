@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{error, fmt, result};
+use core::{error, fmt, result};
+
+#[cfg(feature = "std")]
+use crate::backend;
 
 /// Result type for Soter operations.
 pub type Result<T> = result::Result<T, Error>;
@@ -30,13 +33,22 @@ pub type Result<T> = result::Result<T, Error>;
 /// more as a debugging aid than anything. A proper response to errors is to abort the
 /// high-level operation you are performing, do not trust the source of the input data,
 /// and report this incident to the authorities.
+///
+/// Errors reported by the cryptographic backend are available as the error [`source`].
+///
+/// [`source`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.source
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
+    #[cfg(feature = "std")]
+    source: Option<backend::Error>,
 }
 
 /// List of Soter error categories.
+///
+/// More error kinds may be added in the future, so make sure to handle unknown ones.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// General failure.
     ///
@@ -71,9 +83,53 @@ pub enum ErrorKind {
     /// Usually these are not recoverable at the application level. Consulting with your
     /// system administrator might help to avoid this failure.
     NotSupported,
+    /// Authentication failed.
+    ///
+    /// Integrity check of the data has failed. The data may have been tampered with,
+    /// corrupted in transit, or processed with a wrong key or context. In any case,
+    /// it cannot be trusted.
+    ///
+    /// This is reported separately from `MalformedData` to help with diagnostics.
+    /// Do not make security decisions based on the difference between the two:
+    /// reject the data either way.
+    AuthenticationFailed,
+    /// Malformed data.
+    ///
+    /// Input data cannot be parsed: it is truncated, has invalid headers, or otherwise
+    /// does not look like what it claims to be.
+    MalformedData,
+    /// Keys do not match.
+    ///
+    /// Provided key cannot be used with this data or with the other key, for example,
+    /// it has a different type or belongs to a different key pair.
+    KeyMismatch,
+    /// Invalid state.
+    ///
+    /// The operation is not allowed in the current state of the object or protocol,
+    /// for example, when a computation has already been finalised or a message comes
+    /// out of order. This usually indicates a programming error.
+    ProtocolState,
+    /// Random number generator failed.
+    ///
+    /// The system was unable to provide enough random data. This is not recoverable
+    /// at the application level.
+    RandomFailure,
 }
 
-impl error::Error for Error {}
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        #[cfg(feature = "std")]
+        {
+            self.source
+                .as_ref()
+                .map(|e| e as &(dyn error::Error + 'static))
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            None
+        }
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -82,6 +138,11 @@ impl fmt::Display for Error {
             ErrorKind::InvalidParameter => write!(f, "invalid parameter"),
             ErrorKind::BufferTooSmall(min) => write!(f, "buffer too small, need {} bytes", min),
             ErrorKind::NotSupported => write!(f, "operation not supported"),
+            ErrorKind::AuthenticationFailed => write!(f, "authentication failed"),
+            ErrorKind::MalformedData => write!(f, "malformed data"),
+            ErrorKind::KeyMismatch => write!(f, "keys do not match"),
+            ErrorKind::ProtocolState => write!(f, "invalid state"),
+            ErrorKind::RandomFailure => write!(f, "random number generator failed"),
         }
     }
 }
@@ -89,7 +150,17 @@ impl fmt::Display for Error {
 impl Error {
     /// Constructs a new error of given kind.
     pub(crate) fn new(kind: ErrorKind) -> Error {
-        Error { kind }
+        Error {
+            kind,
+            #[cfg(feature = "std")]
+            source: None,
+        }
+    }
+
+    /// Replaces the kind of this error, keeping its source.
+    #[cfg(feature = "std")]
+    pub(crate) fn with_kind(self, kind: ErrorKind) -> Error {
+        Error { kind, ..self }
     }
 
     /// Returns the corresponding `ErrorKind` for this error.
@@ -98,15 +169,40 @@ impl Error {
     }
 }
 
-impl From<boringssl::Error> for Error {
-    fn from(other: boringssl::Error) -> Error {
+#[cfg(feature = "std")]
+impl From<backend::Error> for Error {
+    fn from(other: backend::Error) -> Error {
         // The mapping is mostly one-to-one.
         let kind = match other.kind() {
-            boringssl::ErrorKind::Failure => ErrorKind::Failure,
-            boringssl::ErrorKind::InvalidParameter => ErrorKind::InvalidParameter,
-            boringssl::ErrorKind::BufferTooSmall(s) => ErrorKind::BufferTooSmall(s),
-            boringssl::ErrorKind::NotSupported => ErrorKind::NotSupported,
+            backend::ErrorKind::Failure => ErrorKind::Failure,
+            backend::ErrorKind::InvalidParameter => ErrorKind::InvalidParameter,
+            backend::ErrorKind::BufferTooSmall(s) => ErrorKind::BufferTooSmall(s),
+            backend::ErrorKind::NotSupported => ErrorKind::NotSupported,
         };
-        Error::new(kind)
+        Error {
+            kind,
+            source: Some(other),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::error::Error as _;
+
+    #[test]
+    fn backend_error_source() {
+        let err = Error::new(ErrorKind::Failure);
+        assert!(err.source().is_none());
+
+        // Unknown curve
+        let err = backend::EC_KEY_new_by_curve_name(0)
+            .map(|_| ())
+            .unwrap_err();
+        let err = Error::from(err);
+        let source = err.source().expect("backend error");
+        assert!(source.is::<backend::Error>());
     }
 }
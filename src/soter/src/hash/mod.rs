@@ -0,0 +1,686 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Computing cryptographic hashes.
+
+use std::cell::RefCell;
+
+use crate::backend::{
+    EVP_DigestFinal_ex, EVP_DigestInit, EVP_DigestUpdate, EVP_MD_CTX_create, EVP_MD_CTX_size,
+    EVP_sha256, EVP_sha512, EVP_MD, EVP_MD_CTX,
+};
+
+use crate::error::{Error, ErrorKind, Result};
+
+use self::sm3::Sm3;
+
+mod sm3;
+
+/// Algorithms supported by [`Hash`].
+///
+/// [`Hash`]: struct.Hash.html
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Algorithm {
+    SHA256,
+    SHA512,
+    /// SM3 (GB/T 32905-2016), 256-bit output.
+    ///
+    /// SM3 is implemented by Soter itself and is available with any backend.
+    /// It is not approved by FIPS 140, so it is not available with `fips` feature.
+    SM3,
+}
+
+/// Soter hash function.
+///
+/// `Hash` computes hash sums or message digests of byte streams.
+/// The interface is analogous to [`std::hash::Hasher`], but different:
+///
+///   - the output is a byte buffer, not `u64`
+///   - you cannot update the hash after obtaining the result
+///   - the methods may fail (but should not normally)
+///   - British spelling ;)
+///
+/// [`std::hash::Hasher`]: https://doc.rust-lang.org/std/hash/trait.Hasher.html
+///
+/// # Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use soter::hash::{Algorithm, Hash};
+///
+/// let mut hash = Hash::new(Algorithm::SHA256);
+/// hash.write("abc");
+/// let hash = hash.get();
+///
+/// assert_eq!(hash, hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+/// ```
+pub struct Hash {
+    // Always present, taken out only when the context is returned to the pool on drop.
+    ctx: Option<Context>,
+    algorithm: Algorithm,
+    finalised: bool,
+}
+
+impl Hash {
+    /// Prepares a new hash computation with given algorithm.
+    ///
+    /// Digest contexts are reused within a thread, so creating many short-lived hashes is cheap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend fails to initialise the computation.
+    /// Use [`try_new`] if you need to handle this failure.
+    ///
+    /// [`try_new`]: struct.Hash.html#method.try_new
+    pub fn new(algorithm: Algorithm) -> Hash {
+        // Normally this should not fail. Possible reasons include
+        // allocation failure (unrecoverable in current Rust) and
+        // missing support for the algorithm (unrecoverable too,
+        // the build system should have included all algorithms).
+        Hash::try_new(algorithm).expect("failed to make a new Hash")
+    }
+
+    /// Prepares a new hash computation with given algorithm, reporting failures.
+    ///
+    /// This is the same as [`new`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotSupported`] if the algorithm is not available,
+    /// e.g., SM3 when Soter is built with `fips` feature.
+    ///
+    /// [`new`]: struct.Hash.html#method.new
+    /// [`NotSupported`]: ../enum.ErrorKind.html#variant.NotSupported
+    pub fn try_new(algorithm: Algorithm) -> Result<Hash> {
+        if cfg!(feature = "fips") && algorithm == Algorithm::SM3 {
+            return Err(Error::new(ErrorKind::NotSupported));
+        }
+        let ctx = match evp_md(algorithm) {
+            Some(md) => Context::Backend(match acquire_context(algorithm) {
+                Some(ctx) => ctx,
+                None => {
+                    let mut ctx = EVP_MD_CTX_create()?;
+                    EVP_DigestInit(&mut ctx, md)?;
+                    ctx
+                }
+            }),
+            None => Context::Sm3(Sm3::new()),
+        };
+        Ok(Hash {
+            ctx: Some(ctx),
+            algorithm,
+            finalised: false,
+        })
+    }
+
+    fn ctx(&self) -> &Context {
+        self.ctx.as_ref().expect("Hash context present")
+    }
+
+    fn ctx_mut(&mut self) -> &mut Context {
+        self.ctx.as_mut().expect("Hash context present")
+    }
+
+    /// Returns the hash sum of the bytes written.
+    ///
+    /// The result is written into the provided buffer (starting from the beginning)
+    /// and a slice of the buffer with the hash is returned.
+    ///
+    /// # Errors
+    ///
+    /// You cannot [`write`] more data into this `Hash` after it has been finalised.
+    /// In order to compute a new hash, you will have to create a new `Hash`.
+    ///
+    /// You also cannot retrieve the hash value again after finalisation.
+    /// Further calls to [`finalise`] will fail with an error of [`ProtocolState`] kind.
+    ///
+    /// If the buffer is too small for the result to fit, an error of [`BufferTooSmall`] kind
+    /// is returned, indicating the minimum size needed. Hash computation is not finalised
+    /// in this case and you can try getting the result again after reallocation.
+    /// You can also use [`output_size`] to obtain the expected size of the result
+    /// and allocate a suitable buffer beforehand.
+    ///
+    /// [`write`]: struct.Hash.html#method.write
+    /// [`finalise`]: struct.Hash.html#method.finalise
+    /// [`BufferTooSmall`]: ../error/enum.ErrorKind.html#variant.BufferTooSmall
+    /// [`ProtocolState`]: ../error/enum.ErrorKind.html#variant.ProtocolState
+    /// [`output_size`]: struct.Hash.html#method.output_size
+    pub fn finalise<'a>(&mut self, buffer: &'a mut [u8]) -> Result<&'a [u8]> {
+        if self.finalised {
+            return Err(Error::new(ErrorKind::ProtocolState));
+        }
+        let result = match self.ctx_mut() {
+            Context::Backend(ctx) => EVP_DigestFinal_ex(ctx, buffer)?,
+            Context::Sm3(sm3) => sm3.finalise(buffer)?,
+        };
+        self.finalised = true;
+        Ok(result)
+    }
+
+    /// Returns the hash sum of the bytes written.
+    ///
+    /// This is a convenience wrapper over [`finalise`] which returns the result
+    /// in a newly allocated vector, consuming this `Hash` object.
+    ///
+    /// # Panics
+    ///
+    /// It is an error to call this method after calling [`finalise`].
+    /// Use [`try_get`] if you need to handle failures.
+    ///
+    /// [`finalise`]: struct.Hash.html#method.finalise
+    /// [`try_get`]: struct.Hash.html#method.try_get
+    pub fn get(self) -> Vec<u8> {
+        self.try_get().expect("failed to finalise Hash")
+    }
+
+    /// Returns the hash sum of the bytes written, reporting failures.
+    ///
+    /// This is the same as [`get`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Errors are the same as for [`finalise`].
+    ///
+    /// [`get`]: struct.Hash.html#method.get
+    /// [`finalise`]: struct.Hash.html#method.finalise
+    pub fn try_get(mut self) -> Result<Vec<u8>> {
+        let mut result = vec![0; self.output_size()];
+        self.finalise(&mut result)?;
+        Ok(result)
+    }
+
+    /// Writes some data into this `Hash`.
+    ///
+    /// # Panics
+    ///
+    /// It is an error to use this method after calling [`finalise`].
+    /// Use [`try_write`] if you need to handle failures.
+    ///
+    /// [`finalise`]: struct.Hash.html#method.finalise
+    /// [`try_write`]: struct.Hash.html#method.try_write
+    pub fn write(&mut self, bytes: impl AsRef<[u8]>) {
+        if self.finalised {
+            panic!("cannot write into finalised Hash");
+        }
+        // Normally this should never happen. If it does, this is an implementation bug.
+        self.try_write(bytes).expect("failed to update Hash")
+    }
+
+    /// Writes some data into this `Hash`, reporting failures.
+    ///
+    /// This is the same as [`write`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`ProtocolState`] kind if this `Hash` has been finalised.
+    ///
+    /// [`write`]: struct.Hash.html#method.write
+    /// [`ProtocolState`]: ../error/enum.ErrorKind.html#variant.ProtocolState
+    pub fn try_write(&mut self, bytes: impl AsRef<[u8]>) -> Result<()> {
+        if self.finalised {
+            return Err(Error::new(ErrorKind::ProtocolState));
+        }
+        match self.ctx_mut() {
+            Context::Backend(ctx) => EVP_DigestUpdate(ctx, bytes.as_ref())?,
+            Context::Sm3(sm3) => sm3.update(bytes.as_ref()),
+        }
+        Ok(())
+    }
+
+    /// Returns output size of this `Hash` in bytes.
+    pub fn output_size(&self) -> usize {
+        match self.ctx() {
+            Context::Backend(ctx) => EVP_MD_CTX_size(ctx),
+            Context::Sm3(_) => sm3::OUTPUT_SIZE,
+        }
+    }
+}
+
+/// Computes hash sums of many inputs in parallel.
+///
+/// Inputs are distributed over the [rayon] thread pool.
+/// The results are returned in the same order as the inputs.
+///
+/// [rayon]: https://docs.rs/rayon
+///
+/// # Example
+///
+/// ```
+/// use soter::hash::{hash_all, Algorithm, Hash};
+///
+/// let inputs = ["abc", "def", "ghi"];
+/// let hashes = hash_all(Algorithm::SHA256, &inputs);
+///
+/// let mut hash = Hash::new(Algorithm::SHA256);
+/// hash.write("def");
+/// assert_eq!(hashes[1], hash.get());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the backend fails to compute any of the hashes.
+/// Use [`try_hash_all`] if you need to handle this failure.
+///
+/// [`try_hash_all`]: fn.try_hash_all.html
+#[cfg(feature = "parallel")]
+pub fn hash_all<T: AsRef<[u8]> + Sync>(algorithm: Algorithm, inputs: &[T]) -> Vec<Vec<u8>> {
+    try_hash_all(algorithm, inputs).expect("failed to compute hashes")
+}
+
+/// Computes hash sums of many inputs in parallel, reporting failures.
+///
+/// This is the same as [`hash_all`], but returns an error instead of panicking.
+///
+/// # Errors
+///
+/// Errors are the same as for [`Hash::try_new`] and [`Hash::finalise`].
+/// If several computations fail, any one of the errors is returned.
+///
+/// [`hash_all`]: fn.hash_all.html
+/// [`Hash::try_new`]: struct.Hash.html#method.try_new
+/// [`Hash::finalise`]: struct.Hash.html#method.finalise
+#[cfg(feature = "parallel")]
+pub fn try_hash_all<T: AsRef<[u8]> + Sync>(
+    algorithm: Algorithm,
+    inputs: &[T],
+) -> Result<Vec<Vec<u8>>> {
+    use rayon::prelude::*;
+    inputs
+        .par_iter()
+        .map(|input| {
+            let mut hash = Hash::try_new(algorithm)?;
+            hash.try_write(input)?;
+            hash.try_get()
+        })
+        .collect()
+}
+
+impl Drop for Hash {
+    fn drop(&mut self) {
+        if let Some(Context::Backend(ctx)) = self.ctx.take() {
+            release_context(self.algorithm, ctx);
+        }
+    }
+}
+
+// Most algorithms are provided by the backend, but not all backends support SM3,
+// so Soter uses its own implementation of it.
+enum Context {
+    Backend(EVP_MD_CTX),
+    Sm3(Sm3),
+}
+
+fn evp_md(algorithm: Algorithm) -> Option<EVP_MD> {
+    match algorithm {
+        Algorithm::SHA256 => Some(EVP_sha256()),
+        Algorithm::SHA512 => Some(EVP_sha512()),
+        Algorithm::SM3 => None,
+    }
+}
+
+// Allocating and initialising digest contexts is a noticeable cost for short inputs,
+// so each thread keeps a few contexts around for reuse. Contexts are reinitialised
+// when they are returned to the pool. This wipes the digest state (which may be derived
+// from HMAC keys) and makes the context ready for use without another initialisation.
+
+/// Maximum number of idle digest contexts kept by each thread.
+const POOL_CAPACITY: usize = 4;
+
+thread_local! {
+    static POOL: RefCell<Vec<(Algorithm, EVP_MD_CTX)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn acquire_context(algorithm: Algorithm) -> Option<EVP_MD_CTX> {
+    POOL.try_with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let index = pool.iter().position(|(a, _)| *a == algorithm)?;
+        Some(pool.swap_remove(index).1)
+    })
+    .ok()
+    .flatten()
+}
+
+fn release_context(algorithm: Algorithm, mut ctx: EVP_MD_CTX) {
+    let md = match evp_md(algorithm) {
+        Some(md) => md,
+        None => return,
+    };
+    // Contexts which cannot be reinitialised are not reused, just let them go.
+    if EVP_DigestInit(&mut ctx, md).is_err() {
+        return;
+    }
+    // The pool may be already destroyed if the thread is exiting.
+    let _ = POOL.try_with(|pool| {
+        let mut pool = pool.borrow_mut();
+        if pool.len() < POOL_CAPACITY {
+            pool.push((algorithm, ctx));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    // We use quite long literals and hex_literal::hex! converts them into arrays,
+    // but Rust arrays have poor support in generics sometimes, so here's a helper
+    // macro hex! which is like hex_literal::hex!, but always outputs slices.
+    macro_rules! hex {
+        ($literal:expr) => {
+            &hex_literal::hex!($literal)[..]
+        };
+    }
+
+    // Test vectors provided by NIST et al.:
+    // https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    // https://www.di-mgt.com.au/sha_testvectors.html
+
+    mod sha256 {
+        use super::super::*;
+
+        #[test]
+        fn test_vectors() {
+            let test_vectors: &[(&[u8], &str)] = &[
+                (hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"), ""),
+                (hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"), "abc"),
+                (hex!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"), "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+                (hex!("cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"), "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"),
+            ];
+            for (expected_output, input) in test_vectors {
+                let mut hash = Hash::new(Algorithm::SHA256);
+                hash.write(input);
+                assert_eq!(hash.get(), *expected_output);
+            }
+        }
+
+        #[test]
+        fn test_vectors_megabyte() {
+            let expected_output =
+                hex!("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+            let pattern = "a".repeat(1000);
+            let mut hash = Hash::new(Algorithm::SHA256);
+            for _ in 0..1000 {
+                hash.write(&pattern);
+            }
+            assert_eq!(hash.get(), expected_output);
+        }
+
+        #[test]
+        #[cfg_attr(not(feature = "long_tests"), ignore)]
+        fn test_vectors_gigabyte() {
+            let expected_output =
+                hex!("50e72a0e26442fe2552dc3938ac58658228c0cbfb1d2ca872ae435266fcd055e");
+            let pattern = "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno";
+            let mut hash = Hash::new(Algorithm::SHA256);
+            for _ in 0..16777216 {
+                hash.write(pattern);
+            }
+            assert_eq!(hash.get(), expected_output);
+        }
+    }
+
+    mod sha512 {
+        use super::super::*;
+
+        #[test]
+        fn test_vectors() {
+            let test_vectors: &[(&[u8], &str)] = &[
+                (hex!("cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"), ""),
+                (hex!("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"), "abc"),
+                (hex!("204a8fc6dda82f0a0ced7beb8e08a41657c16ef468b228a8279be331a703c33596fd15c13b1b07f9aa1d3bea57789ca031ad85c7a71dd70354ec631238ca3445"), "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+                (hex!("8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"), "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"),
+            ];
+            for (expected_output, input) in test_vectors {
+                let mut hash = Hash::new(Algorithm::SHA512);
+                hash.write(input);
+                assert_eq!(hash.get(), *expected_output);
+            }
+        }
+
+        #[test]
+        fn test_vectors_megabyte() {
+            let expected_output = hex!("e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b");
+            let pattern = "a".repeat(1000);
+            let mut hash = Hash::new(Algorithm::SHA512);
+            for _ in 0..1000 {
+                hash.write(&pattern);
+            }
+            assert_eq!(hash.get(), expected_output);
+        }
+
+        #[test]
+        #[cfg_attr(not(feature = "long_tests"), ignore)]
+        fn test_vectors_gigabyte() {
+            let expected_output = hex!("b47c933421ea2db149ad6e10fce6c7f93d0752380180ffd7f4629a712134831d77be6091b819ed352c2967a2e2d4fa5050723c9630691f1a05a7281dbe6c1086");
+            let pattern = "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno";
+            let mut hash = Hash::new(Algorithm::SHA512);
+            for _ in 0..16777216 {
+                hash.write(pattern);
+            }
+            assert_eq!(hash.get(), expected_output);
+        }
+    }
+
+    // SM3 test vectors from GB/T 32905-2016, appendix A, and computed with OpenSSL.
+    #[cfg(not(feature = "fips"))]
+    mod sm3 {
+        use super::super::*;
+
+        #[test]
+        fn test_vectors() {
+            let test_vectors: &[(&[u8], &str)] = &[
+                (
+                    hex!("1ab21d8355cfa17f8e61194831e81a8f22bec8c728fefb747ed035eb5082aa2b"),
+                    "",
+                ),
+                (
+                    hex!("66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"),
+                    "abc",
+                ),
+                (
+                    hex!("debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732"),
+                    "abcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcd",
+                ),
+                (
+                    hex!("639b6cc5e64d9e37a390b192df4fa1ea0720ab747ff692b9f38c4e66ad7b8c05"),
+                    "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                ),
+                (
+                    hex!("288337eef51eec62e7544d7270424c8dbe656254c99852870a73b2453a6a7fb1"),
+                    "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                ),
+                (
+                    hex!("ba00ebedaab54065a5fd4f9f56326016203166bcee3eed44ea868d59d67aa3c8"),
+                    "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                ),
+            ];
+            for (expected_output, input) in test_vectors {
+                let mut hash = Hash::new(Algorithm::SM3);
+                hash.write(input);
+                assert_eq!(hash.get(), *expected_output);
+            }
+        }
+
+        #[test]
+        fn test_vectors_megabyte() {
+            let expected_output =
+                hex!("c8aaf89429554029e231941a2acc0ad61ff2a5acd8fadd25847a3a732b3b02c3");
+            let pattern = "a".repeat(1000);
+            let mut hash = Hash::new(Algorithm::SM3);
+            for _ in 0..1000 {
+                hash.write(&pattern);
+            }
+            assert_eq!(hash.get(), expected_output);
+        }
+
+        #[test]
+        fn byte_by_byte() {
+            let expected_output =
+                hex!("639b6cc5e64d9e37a390b192df4fa1ea0720ab747ff692b9f38c4e66ad7b8c05");
+            let input = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+            let mut hash = Hash::new(Algorithm::SM3);
+            for byte in input.iter() {
+                hash.write([*byte]);
+            }
+            assert_eq!(hash.get(), expected_output);
+        }
+    }
+
+    #[test]
+    fn output_sizes() {
+        assert_eq!(Hash::new(Algorithm::SHA256).output_size(), 256 / 8);
+        assert_eq!(Hash::new(Algorithm::SHA512).output_size(), 512 / 8);
+        #[cfg(not(feature = "fips"))]
+        assert_eq!(Hash::new(Algorithm::SM3).output_size(), 256 / 8);
+    }
+
+    #[test]
+    #[cfg(feature = "fips")]
+    fn fips_sm3() {
+        let err = Hash::try_new(Algorithm::SM3)
+            .map(|_| ())
+            .expect_err("SM3 is not approved");
+        assert_eq!(err.kind(), ErrorKind::NotSupported);
+    }
+
+    #[test]
+    fn cannot_finalise_twice() {
+        let mut hash = Hash::new(Algorithm::SHA512);
+        let mut output = [0; 512 / 8];
+        assert!(hash.finalise(&mut output).is_ok());
+        let err = hash.finalise(&mut output).expect_err("already finalised");
+        assert_eq!(err.kind(), ErrorKind::ProtocolState);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot write into finalised Hash")]
+    fn cannot_write_past_finalise() {
+        let mut hash = Hash::new(Algorithm::SHA256);
+        let mut output = [0; 256 / 8];
+        assert!(hash.finalise(&mut output).is_ok());
+        hash.write(b"abc"); // should panic
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to finalise Hash")]
+    fn cannot_get_after_finalise() {
+        let mut hash = Hash::new(Algorithm::SHA256);
+        let mut output = [0; 256 / 8];
+        assert!(hash.finalise(&mut output).is_ok());
+        let _ = hash.get(); // should panic
+    }
+
+    #[test]
+    fn try_methods() {
+        let mut hash = Hash::try_new(Algorithm::SHA256).expect("new Hash");
+        hash.try_write(b"abc").expect("write");
+        assert_eq!(
+            hash.try_get().expect("get"),
+            hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+    }
+
+    #[test]
+    fn try_methods_after_finalise() {
+        let mut hash = Hash::try_new(Algorithm::SHA256).expect("new Hash");
+        let mut output = [0; 256 / 8];
+        assert!(hash.finalise(&mut output).is_ok());
+        let err = hash.try_write(b"abc").expect_err("already finalised");
+        assert_eq!(err.kind(), ErrorKind::ProtocolState);
+        let err = hash.try_get().expect_err("already finalised");
+        assert_eq!(err.kind(), ErrorKind::ProtocolState);
+    }
+
+    fn pooled_contexts() -> usize {
+        POOL.with(|pool| pool.borrow().len())
+    }
+
+    #[test]
+    fn contexts_are_reused() {
+        // Each test runs in its own thread, starting with an empty pool.
+        assert_eq!(pooled_contexts(), 0);
+
+        let mut hash = Hash::new(Algorithm::SHA256);
+        hash.write("abc");
+        drop(hash);
+        assert_eq!(pooled_contexts(), 1);
+
+        // Different algorithm does not use the pooled context.
+        let hash512 = Hash::new(Algorithm::SHA512);
+        assert_eq!(pooled_contexts(), 1);
+
+        // Pooled context does not retain data from the previous computation.
+        let hash256 = Hash::new(Algorithm::SHA256);
+        assert_eq!(pooled_contexts(), 0);
+        let empty_sha256 = hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(hash256.get(), empty_sha256);
+
+        drop(hash512);
+        assert_eq!(pooled_contexts(), 2);
+    }
+
+    #[test]
+    fn pool_is_bounded() {
+        let hashes: Vec<_> = (0..2 * POOL_CAPACITY)
+            .map(|_| Hash::new(Algorithm::SHA512))
+            .collect();
+        drop(hashes);
+        assert_eq!(pooled_contexts(), POOL_CAPACITY);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_same_as_sequential() {
+        let inputs: Vec<Vec<u8>> = (0..1000).map(|i| vec![i as u8; i]).collect();
+        let hashes = hash_all(Algorithm::SHA512, &inputs);
+        assert_eq!(hashes.len(), inputs.len());
+        for (input, parallel) in inputs.iter().zip(&hashes) {
+            let mut hash = Hash::new(Algorithm::SHA512);
+            hash.write(input);
+            assert_eq!(*parallel, hash.get());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_try_same_as_sequential() {
+        let inputs = ["abc", "def", "ghi"];
+        let hashes = try_hash_all(Algorithm::SHA256, &inputs).expect("hashes");
+        assert_eq!(hashes, hash_all(Algorithm::SHA256, &inputs));
+    }
+
+    #[test]
+    fn finalise_short() {
+        let mut hash = Hash::new(Algorithm::SHA256);
+        let mut output = [0; 128 / 8];
+        // You can't finalise if the buffer is too small.
+        let err = hash.finalise(&mut output).expect_err("not enough buffer");
+        assert_eq!(err.kind(), ErrorKind::BufferTooSmall(256 / 8));
+        // But you can after you reallocate.
+        let mut output = [0; 256 / 8];
+        assert!(hash.finalise(&mut output).is_ok());
+    }
+
+    #[test]
+    fn finalise_long() {
+        let mut hash = Hash::new(Algorithm::SHA256);
+        let mut output = [0xED; 512 / 8];
+        let result = hash.finalise(&mut output).expect("big buffer is fine");
+        // If the buffer is bigger than necessary, it's only partially filled.
+        let empty_sha256 = hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(result, empty_sha256);
+        assert_eq!(&output[32..64], [0xED; 32]);
+    }
+}
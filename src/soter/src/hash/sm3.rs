@@ -0,0 +1,182 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SM3 hash function.
+//!
+//! SM3 is a Chinese national standard ([GB/T 32905-2016], also [ISO/IEC 10118-3:2018]).
+//! Most backends do not provide it, so Soter has its own implementation.
+//!
+//! [GB/T 32905-2016]: https://datatracker.ietf.org/doc/html/draft-sca-cfrg-sm3-02
+//! [ISO/IEC 10118-3:2018]: https://www.iso.org/standard/67116.html
+
+use zeroize::Zeroize;
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Size of SM3 hash value in bytes.
+pub(crate) const OUTPUT_SIZE: usize = 32;
+
+const BLOCK_SIZE: usize = 64;
+
+const IV: [u32; 8] = [
+    0x7380_166F,
+    0x4914_B2B9,
+    0x1724_42D7,
+    0xDA8A_0600,
+    0xA96F_30BC,
+    0x1631_38AA,
+    0xE38D_EE4D,
+    0xB0FB_0E4E,
+];
+
+const T_0_15: u32 = 0x79CC_4519;
+const T_16_63: u32 = 0x7A87_9D8A;
+
+/// SM3 computation state.
+pub(crate) struct Sm3 {
+    state: [u32; 8],
+    buffer: [u8; BLOCK_SIZE],
+    buffered: usize,
+    // Total input length in bytes.
+    length: u64,
+}
+
+impl Sm3 {
+    pub(crate) fn new() -> Sm3 {
+        Sm3 {
+            state: IV,
+            buffer: [0; BLOCK_SIZE],
+            buffered: 0,
+            length: 0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len() as u64);
+
+        if self.buffered > 0 {
+            let take = bytes.len().min(BLOCK_SIZE - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&bytes[..take]);
+            self.buffered += take;
+            bytes = &bytes[take..];
+            if self.buffered < BLOCK_SIZE {
+                return;
+            }
+            compress(&mut self.state, &self.buffer);
+            self.buffered = 0;
+        }
+
+        let mut blocks = bytes.chunks_exact(BLOCK_SIZE);
+        for block in &mut blocks {
+            compress(&mut self.state, block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// Writes the hash value into the buffer and returns a subslice with it.
+    ///
+    /// The state is wiped afterwards, so the value cannot be retrieved again.
+    pub(crate) fn finalise<'a>(&mut self, buffer: &'a mut [u8]) -> Result<&'a [u8]> {
+        if buffer.len() < OUTPUT_SIZE {
+            return Err(Error::new(ErrorKind::BufferTooSmall(OUTPUT_SIZE)));
+        }
+        let bit_length = self.length.wrapping_mul(8);
+
+        // Padding: a single 1 bit, zeros, and 64-bit big-endian length of the input in bits.
+        self.buffer[self.buffered] = 0x80;
+        self.buffer[self.buffered + 1..].fill(0);
+        if self.buffered + 1 > BLOCK_SIZE - 8 {
+            compress(&mut self.state, &self.buffer);
+            self.buffer.fill(0);
+        }
+        self.buffer[BLOCK_SIZE - 8..].copy_from_slice(&bit_length.to_be_bytes());
+        compress(&mut self.state, &self.buffer);
+
+        let result = &mut buffer[..OUTPUT_SIZE];
+        for (chunk, word) in result.chunks_exact_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        self.wipe();
+        Ok(result)
+    }
+
+    fn wipe(&mut self) {
+        self.state.zeroize();
+        self.buffer.zeroize();
+        self.buffered = 0;
+        self.length = 0;
+    }
+}
+
+// Hash state may be derived from HMAC keys, do not leave it in memory.
+impl Drop for Sm3 {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+fn p0(x: u32) -> u32 {
+    x ^ x.rotate_left(9) ^ x.rotate_left(17)
+}
+
+fn p1(x: u32) -> u32 {
+    x ^ x.rotate_left(15) ^ x.rotate_left(23)
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 68];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for j in 16..68 {
+        w[j] = p1(w[j - 16] ^ w[j - 9] ^ w[j - 3].rotate_left(15))
+            ^ w[j - 13].rotate_left(7)
+            ^ w[j - 6];
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for j in 0..64 {
+        let (t, ff, gg) = if j < 16 {
+            (T_0_15, a ^ b ^ c, e ^ f ^ g)
+        } else {
+            (T_16_63, (a & b) | (a & c) | (b & c), (e & f) | (!e & g))
+        };
+        let a12 = a.rotate_left(12);
+        let ss1 = a12
+            .wrapping_add(e)
+            .wrapping_add(t.rotate_left(j as u32 % 32))
+            .rotate_left(7);
+        let ss2 = ss1 ^ a12;
+        let tt1 = ff
+            .wrapping_add(d)
+            .wrapping_add(ss2)
+            .wrapping_add(w[j] ^ w[j + 4]);
+        let tt2 = gg.wrapping_add(h).wrapping_add(ss1).wrapping_add(w[j]);
+        d = c;
+        c = b.rotate_left(9);
+        b = a;
+        a = tt1;
+        h = g;
+        g = f.rotate_left(19);
+        f = e;
+        e = p0(tt2);
+    }
+
+    for (v, x) in state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+        *v ^= x;
+    }
+    w.zeroize();
+}
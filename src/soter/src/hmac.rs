@@ -0,0 +1,327 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message authentication with HMAC.
+
+use std::fmt;
+
+use zeroize::Zeroizing;
+
+use crate::error::Result;
+use crate::hash::{Algorithm, Hash};
+
+/// HMAC computation.
+///
+/// `Hmac` computes keyed message authentication codes as defined by [RFC 2104],
+/// using one of the [`Hash`] algorithms. The interface is the same as that of `Hash`.
+///
+/// [RFC 2104]: https://tools.ietf.org/html/rfc2104
+/// [`Hash`]: ../hash/struct.Hash.html
+///
+/// # Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use soter::hash::Algorithm;
+/// use soter::hmac::Hmac;
+///
+/// let mut hmac = Hmac::new(Algorithm::SHA256, "key");
+/// hmac.write("The quick brown fox jumps over the lazy dog");
+/// let mac = hmac.get();
+///
+/// assert_eq!(mac, hex!("f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"));
+/// ```
+pub struct Hmac {
+    inner: Hash,
+    outer: Hash,
+}
+
+impl Hmac {
+    /// Prepares a new HMAC computation with given algorithm and key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend fails to initialise the computation.
+    /// Use [`try_new`] if you need to handle this failure.
+    ///
+    /// [`try_new`]: struct.Hmac.html#method.try_new
+    pub fn new(algorithm: Algorithm, key: impl AsRef<[u8]>) -> Hmac {
+        Hmac::try_new(algorithm, key).expect("failed to make a new Hmac")
+    }
+
+    /// Prepares a new HMAC computation with given algorithm and key, reporting failures.
+    ///
+    /// This is the same as [`new`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Errors are the same as for [`Hash::try_new`].
+    ///
+    /// [`new`]: struct.Hmac.html#method.new
+    /// [`Hash::try_new`]: ../hash/struct.Hash.html#method.try_new
+    pub fn try_new(algorithm: Algorithm, key: impl AsRef<[u8]>) -> Result<Hmac> {
+        let key = key.as_ref();
+        let mut block = Zeroizing::new(vec![0; block_size(algorithm)]);
+        // Keys longer than the block size are hashed first.
+        if key.len() > block.len() {
+            let mut hash = Hash::try_new(algorithm)?;
+            hash.try_write(key)?;
+            let digest = Zeroizing::new(hash.try_get()?);
+            block[..digest.len()].copy_from_slice(&digest);
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        for byte in block.iter_mut() {
+            *byte ^= 0x36;
+        }
+        let mut inner = Hash::try_new(algorithm)?;
+        inner.try_write(&*block)?;
+
+        for byte in block.iter_mut() {
+            *byte ^= 0x36 ^ 0x5C;
+        }
+        let mut outer = Hash::try_new(algorithm)?;
+        outer.try_write(&*block)?;
+
+        Ok(Hmac { inner, outer })
+    }
+
+    /// Returns the authentication code of the bytes written.
+    ///
+    /// The result is written into the provided buffer (starting from the beginning)
+    /// and a slice of the buffer with the code is returned.
+    ///
+    /// # Errors
+    ///
+    /// Errors are the same as for [`Hash::finalise`].
+    ///
+    /// [`Hash::finalise`]: ../hash/struct.Hash.html#method.finalise
+    pub fn finalise<'a>(&mut self, buffer: &'a mut [u8]) -> Result<&'a [u8]> {
+        self.inner.finalise(buffer)?;
+        self.outer.try_write(&buffer[..self.output_size()])?;
+        self.outer.finalise(buffer)
+    }
+
+    /// Returns the authentication code of the bytes written.
+    ///
+    /// This is a convenience wrapper over [`finalise`] which returns the result
+    /// in a newly allocated vector, consuming this `Hmac` object.
+    ///
+    /// # Panics
+    ///
+    /// It is an error to call this method after calling [`finalise`].
+    /// Use [`try_get`] if you need to handle failures.
+    ///
+    /// [`finalise`]: struct.Hmac.html#method.finalise
+    /// [`try_get`]: struct.Hmac.html#method.try_get
+    pub fn get(self) -> Vec<u8> {
+        self.try_get().expect("failed to finalise Hmac")
+    }
+
+    /// Returns the authentication code of the bytes written, reporting failures.
+    ///
+    /// This is the same as [`get`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Errors are the same as for [`finalise`].
+    ///
+    /// [`get`]: struct.Hmac.html#method.get
+    /// [`finalise`]: struct.Hmac.html#method.finalise
+    pub fn try_get(mut self) -> Result<Vec<u8>> {
+        let mut result = vec![0; self.output_size()];
+        self.finalise(&mut result)?;
+        Ok(result)
+    }
+
+    /// Writes some data into this `Hmac`.
+    ///
+    /// # Panics
+    ///
+    /// It is an error to use this method after calling [`finalise`].
+    /// Use [`try_write`] if you need to handle failures.
+    ///
+    /// [`finalise`]: struct.Hmac.html#method.finalise
+    /// [`try_write`]: struct.Hmac.html#method.try_write
+    pub fn write(&mut self, bytes: impl AsRef<[u8]>) {
+        self.inner.write(bytes)
+    }
+
+    /// Writes some data into this `Hmac`, reporting failures.
+    ///
+    /// This is the same as [`write`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Errors are the same as for [`Hash::try_write`].
+    ///
+    /// [`write`]: struct.Hmac.html#method.write
+    /// [`Hash::try_write`]: ../hash/struct.Hash.html#method.try_write
+    pub fn try_write(&mut self, bytes: impl AsRef<[u8]>) -> Result<()> {
+        self.inner.try_write(bytes)
+    }
+
+    /// Returns output size of this `Hmac` in bytes.
+    pub fn output_size(&self) -> usize {
+        self.outer.output_size()
+    }
+}
+
+// Hash states are derived from the key, never print them out.
+impl fmt::Debug for Hmac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Hmac")
+            .field("output_size", &self.output_size())
+            .finish_non_exhaustive()
+    }
+}
+
+fn block_size(algorithm: Algorithm) -> usize {
+    match algorithm {
+        Algorithm::SHA256 => 64,
+        Algorithm::SHA512 => 128,
+        Algorithm::SM3 => 64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    use hex_literal::hex;
+
+    type TestVector = (&'static [u8], &'static [u8], &'static [u8], &'static [u8]);
+
+    // Test vectors from RFC 4231: https://tools.ietf.org/html/rfc4231
+    // (test case 5 with truncated output is omitted)
+    const TEST_VECTORS: &[TestVector] = &[
+        (
+            &hex!("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b"),
+            b"Hi There",
+            &hex!("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+            &hex!("87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"),
+        ),
+        (
+            b"Jefe",
+            b"what do ya want for nothing?",
+            &hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+            &hex!("164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"),
+        ),
+        (
+            &hex!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            &hex!("dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd"),
+            &hex!("773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe"),
+            &hex!("fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb"),
+        ),
+        (
+            &hex!("0102030405060708090a0b0c0d0e0f10111213141516171819"),
+            &hex!("cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd"),
+            &hex!("82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b"),
+            &hex!("b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd"),
+        ),
+        (
+            &hex!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            &hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+            &hex!("80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"),
+        ),
+        (
+            &hex!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.",
+            &hex!("9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"),
+            &hex!("e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"),
+        ),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for (key, data, sha256, sha512) in TEST_VECTORS {
+            let mut hmac = Hmac::new(Algorithm::SHA256, key);
+            hmac.write(data);
+            assert_eq!(hmac.get(), *sha256);
+
+            let mut hmac = Hmac::new(Algorithm::SHA512, key);
+            hmac.write(data);
+            assert_eq!(hmac.get(), *sha512);
+        }
+    }
+
+    // Computed with OpenSSL, the second key is longer than the block size.
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn sm3_test_vectors() {
+        let mut hmac = Hmac::new(Algorithm::SM3, "Jefe");
+        hmac.write("what do ya want for nothing?");
+        assert_eq!(
+            hmac.get(),
+            hex!("2e87f1d16862e6d964b50a5200bf2b10b764faa9680a296a2405f24bec39f882")
+        );
+
+        let mut hmac = Hmac::new(Algorithm::SM3, [0xAA; 131]);
+        hmac.write("Hi There");
+        assert_eq!(
+            hmac.get(),
+            hex!("e760df9495d6be82b447e1b92b1f5ef7c7fe29fe88607d212b30ee6aae753aeb")
+        );
+    }
+
+    #[test]
+    fn incremental_input() {
+        let mut hmac = Hmac::new(Algorithm::SHA256, "Jefe");
+        hmac.write("what do ya ");
+        hmac.write("");
+        hmac.write("want for nothing?");
+        assert_eq!(hmac.get(), TEST_VECTORS[1].2);
+    }
+
+    #[test]
+    fn finalise_short() {
+        let mut hmac = Hmac::new(Algorithm::SHA256, "key");
+        let mut output = [0; 16];
+        let err = hmac.finalise(&mut output).expect_err("not enough buffer");
+        assert_eq!(err.kind(), ErrorKind::BufferTooSmall(32));
+        let mut output = [0; 32];
+        assert!(hmac.finalise(&mut output).is_ok());
+    }
+
+    #[test]
+    fn try_methods() {
+        let (key, data, sha256, _) = TEST_VECTORS[1];
+        let mut hmac = Hmac::try_new(Algorithm::SHA256, key).expect("new Hmac");
+        hmac.try_write(data).expect("write");
+        assert_eq!(hmac.try_get().expect("get"), sha256);
+    }
+
+    #[test]
+    #[cfg(feature = "fips")]
+    fn fips_sm3() {
+        let err = Hmac::try_new(Algorithm::SM3, "Jefe")
+            .map(|_| ())
+            .expect_err("SM3 is not approved");
+        assert_eq!(err.kind(), ErrorKind::NotSupported);
+    }
+
+    #[test]
+    fn try_methods_after_finalise() {
+        let mut hmac = Hmac::try_new(Algorithm::SHA256, "key").expect("new Hmac");
+        let mut output = [0; 32];
+        assert!(hmac.finalise(&mut output).is_ok());
+        let err = hmac.try_write("data").expect_err("already finalised");
+        assert_eq!(err.kind(), ErrorKind::ProtocolState);
+        let err = hmac.try_get().expect_err("already finalised");
+        assert_eq!(err.kind(), ErrorKind::ProtocolState);
+    }
+}
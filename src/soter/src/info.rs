@@ -0,0 +1,180 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Information about the cryptographic backend.
+
+use crate::backend;
+
+/// Cryptographic capabilities which might be available in Soter.
+///
+/// Use [`BackendInfo::supports`] to check whether the current backend has them.
+///
+/// [`BackendInfo::supports`]: struct.BackendInfo.html#method.supports
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Capability {
+    /// SHA-256 hash function.
+    Sha256,
+    /// SHA-512 hash function.
+    Sha512,
+    /// SM3 hash function.
+    Sm3,
+    /// HMAC with supported hash functions.
+    Hmac,
+    /// Generation of ECDSA key pairs on P-256 curve.
+    EcP256KeyGeneration,
+    /// Generation of RSA key pairs.
+    RsaKeyGeneration,
+}
+
+const ALL_CAPABILITIES: &[Capability] = &[
+    Capability::Sha256,
+    Capability::Sha512,
+    Capability::Sm3,
+    Capability::Hmac,
+    Capability::EcP256KeyGeneration,
+    Capability::RsaKeyGeneration,
+];
+
+/// Information about the cryptographic backend used by Soter.
+///
+/// Use [`backend_info`] to obtain it.
+///
+/// [`backend_info`]: fn.backend_info.html
+#[derive(Debug, Clone)]
+pub struct BackendInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    fips: bool,
+}
+
+/// Returns information about the cryptographic backend in use.
+///
+/// # Examples
+///
+/// ```
+/// use soter::Capability;
+///
+/// let backend = soter::backend_info();
+///
+/// println!("Soter uses {}", backend.name());
+///
+/// if backend.supports(Capability::RsaKeyGeneration) {
+///     // ...
+/// }
+/// ```
+pub fn backend_info() -> BackendInfo {
+    BackendInfo {
+        name: backend::BACKEND_NAME,
+        version: backend::backend_version(),
+        fips: backend::FIPS_mode(),
+    }
+}
+
+impl BackendInfo {
+    /// Returns name of the backend library, e.g., "BoringSSL".
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns version of the backend library, if it is known.
+    ///
+    /// The format of the version string is specific to the backend.
+    pub fn version(&self) -> Option<&'static str> {
+        self.version
+    }
+
+    /// Returns `true` if the backend is running in FIPS mode.
+    ///
+    /// This is the same as [`is_fips`].
+    ///
+    /// [`is_fips`]: fn.is_fips.html
+    pub fn is_fips(&self) -> bool {
+        self.fips
+    }
+
+    /// Checks whether the backend has given capability.
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            // ring cannot generate RSA keys.
+            Capability::RsaKeyGeneration => {
+                cfg!(feature = "rsa") && !cfg!(feature = "backend-ring")
+            }
+            // SM3 is not approved by FIPS 140.
+            Capability::Sm3 => !cfg!(feature = "fips"),
+            _ => true,
+        }
+    }
+
+    /// Returns all capabilities supported by the backend.
+    pub fn capabilities(&self) -> impl Iterator<Item = Capability> + '_ {
+        ALL_CAPABILITIES
+            .iter()
+            .copied()
+            .filter(move |&capability| self.supports(capability))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ErrorKind;
+
+    #[test]
+    fn name() {
+        let expected = if cfg!(feature = "backend-aws-lc") {
+            "AWS-LC"
+        } else if cfg!(feature = "backend-openssl") {
+            "OpenSSL"
+        } else if cfg!(feature = "backend-ring") {
+            "ring"
+        } else {
+            "BoringSSL"
+        };
+        assert_eq!(backend_info().name(), expected);
+    }
+
+    #[test]
+    fn capabilities_are_consistent() {
+        let info = backend_info();
+        for capability in info.capabilities() {
+            assert!(info.supports(capability));
+        }
+        assert!(info.supports(Capability::Sha256));
+    }
+
+    #[test]
+    fn rsa_key_generation() {
+        let info = backend_info();
+        let result = crate::rsa::KeyPair::generate(2048);
+        if info.supports(Capability::RsaKeyGeneration) {
+            assert!(result.is_ok());
+        } else {
+            let err = result.map(|_| ()).expect_err("RSA key generation");
+            assert_eq!(err.kind(), ErrorKind::NotSupported);
+        }
+    }
+
+    #[test]
+    fn sm3() {
+        let info = backend_info();
+        let result = crate::hash::Hash::try_new(crate::hash::Algorithm::SM3);
+        if info.supports(Capability::Sm3) {
+            assert!(result.is_ok());
+        } else {
+            let err = result.map(|_| ()).expect_err("SM3");
+            assert_eq!(err.kind(), ErrorKind::NotSupported);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key derivation.
+//!
+//! Soter KDF is a single-step HMAC-based key derivation function, modelled after
+//! the one used in ZRTP ([RFC 6189, section 4.5.1][ZRTP]). It is compatible with
+//! `soter_kdf()` of C Themis:
+//!
+//! ```text
+//! output = HMAC-SHA256(key, 00000001 || label || 00 || context...)
+//! ```
+//!
+//! truncated to the requested length.
+//!
+//! [ZRTP]: https://tools.ietf.org/html/rfc6189#section-4.5.1
+
+use zeroize::Zeroizing;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hash::Algorithm;
+use crate::hmac::Hmac;
+
+/// Maximum output length of the KDF, in bytes.
+pub const MAX_OUTPUT_LENGTH: usize = 32;
+
+const IMPLICIT_KEY_LENGTH: usize = 32;
+
+/// Derives key material.
+///
+/// Derives `output.len()` bytes of key material from the `key`, using `label` and `context`
+/// to distinguish between keys derived from the same input for different purposes.
+/// The same input always produces the same output.
+///
+/// If `key` is `None` then an implicit key is computed from the label and context.
+/// This is useful for deriving non-secret values, such as identifiers.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if `output` is longer than
+/// [`MAX_OUTPUT_LENGTH`].
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+/// [`MAX_OUTPUT_LENGTH`]: constant.MAX_OUTPUT_LENGTH.html
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> soter::Result<()> {
+/// use soter::kdf;
+///
+/// let mut key = [0; 32];
+/// kdf::derive(Some(b"master key"), "encryption", &[b"user #42"], &mut key)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn derive(
+    key: Option<&[u8]>,
+    label: impl AsRef<[u8]>,
+    context: &[&[u8]],
+    output: &mut [u8],
+) -> Result<()> {
+    if output.len() > MAX_OUTPUT_LENGTH {
+        return Err(Error::new(ErrorKind::InvalidParameter));
+    }
+    let label = label.as_ref();
+
+    let mut implicit_key = Zeroizing::new([0; IMPLICIT_KEY_LENGTH]);
+    let key = match key {
+        Some(key) => key,
+        None => {
+            let prefix = label.len().min(IMPLICIT_KEY_LENGTH);
+            implicit_key[..prefix].copy_from_slice(&label[..prefix]);
+            for item in context {
+                for (k, c) in implicit_key.iter_mut().zip(item.iter()) {
+                    *k ^= c;
+                }
+            }
+            &implicit_key[..]
+        }
+    };
+
+    let mut hmac = Hmac::try_new(Algorithm::SHA256, key)?;
+    hmac.try_write([0, 0, 0, 1])?;
+    hmac.try_write(label)?;
+    hmac.try_write([0])?;
+    for item in context {
+        hmac.try_write(item)?;
+    }
+    let result = Zeroizing::new(hmac.try_get()?);
+    output.copy_from_slice(&result[..output.len()]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hex_literal::hex;
+
+    // Expected values computed with an independent implementation (Python's hmac module).
+
+    #[test]
+    fn explicit_key() {
+        let mut output = [0; 32];
+        derive(Some(b"key"), "label", &[b"context"], &mut output).unwrap();
+        assert_eq!(
+            output,
+            hex!("19bdc672b44386a16d24fc66994d9ef8accf1ed1caa159904bac6d59107f7669")
+        );
+    }
+
+    #[test]
+    fn implicit_key() {
+        let mut output = [0; 32];
+        derive(None, "label", &[b"context", b"more"], &mut output).unwrap();
+        assert_eq!(
+            output,
+            hex!("a04ec8151dea9b0edc0a43538bd4836cccdb0954565f42fc920ab03068c64954")
+        );
+    }
+
+    #[test]
+    fn truncated_output() {
+        let mut full = [0; 32];
+        let mut short = [0; 16];
+        derive(Some(b"key"), "label", &[], &mut full).unwrap();
+        derive(Some(b"key"), "label", &[], &mut short).unwrap();
+        assert_eq!(short, full[..16]);
+    }
+
+    #[test]
+    fn context_matters() {
+        let mut output_1 = [0; 32];
+        let mut output_2 = [0; 32];
+        derive(Some(b"key"), "label", &[b"one"], &mut output_1).unwrap();
+        derive(Some(b"key"), "label", &[b"two"], &mut output_2).unwrap();
+        assert_ne!(output_1, output_2);
+    }
+
+    #[test]
+    fn output_too_long() {
+        let mut output = [0; 33];
+        let err = derive(Some(b"key"), "label", &[], &mut output).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+    }
+}
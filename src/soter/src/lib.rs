@@ -12,10 +12,143 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Soter is a cryptographic library backing Themis.
+//!
+//! Most of the functionality is provided by a cryptographic backend.
+//! Backends require the standard library (`std` feature, enabled by any backend).
+//! Without it Soter is built with `no_std` and `alloc`, and only software
+//! implementations are provided: [`crc`], [`container`], [`encoding`], and [`memory`]
+//! modules. This is the only option for WebAssembly (`wasm32`) builds.
+//!
+//! [`crc`]: crc/index.html
+//! [`container`]: container/index.html
+//! [`encoding`]: encoding/index.html
+//! [`memory`]: memory/index.html
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+// Runtime CPU feature detection requires the standard library. Without it, only the features
+// enabled at compile time (e.g., with "-C target-cpu") are used.
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! x86_feature_detected {
+    ($($feature:tt),+) => {
+        $(is_x86_feature_detected!($feature))&&+
+    };
+}
+#[cfg(all(not(feature = "std"), any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! x86_feature_detected {
+    ($($feature:tt),+) => {
+        cfg!(all($(target_feature = $feature),+))
+    };
+}
+
+pub mod container;
 pub mod crc;
+#[cfg(feature = "std")]
+pub mod ec;
+pub mod encoding;
+#[cfg(feature = "std")]
 pub mod hash;
+#[cfg(feature = "std")]
+pub mod hmac;
+#[cfg(feature = "std")]
+pub mod kdf;
+pub mod memory;
+#[cfg(feature = "std")]
 pub mod rand;
+#[cfg(feature = "std")]
+pub mod rsa;
+#[cfg(feature = "std")]
+pub mod shamir;
 
 mod error;
+#[cfg(feature = "std")]
+mod info;
+mod self_test;
+
+// Soter needs exactly one cryptographic backend. BoringSSL is used by default,
+// others can be selected with "default-features = false" and a "backend-*" feature.
+// Software-only builds are selected with "default-features = false" alone.
+#[cfg(all(
+    feature = "std",
+    not(any(
+        feature = "backend-aws-lc",
+        feature = "backend-boringssl",
+        feature = "backend-openssl",
+        feature = "backend-ring",
+    ))
+))]
+compile_error!(
+    "no cryptographic backend selected for Soter, \
+     enable one of the features: backend-aws-lc, backend-boringssl, backend-openssl, backend-ring \
+     (or disable \"std\" feature for a software-only build)"
+);
+
+#[cfg(any(
+    all(feature = "backend-aws-lc", feature = "backend-boringssl"),
+    all(feature = "backend-aws-lc", feature = "backend-openssl"),
+    all(feature = "backend-aws-lc", feature = "backend-ring"),
+    all(feature = "backend-boringssl", feature = "backend-openssl"),
+    all(feature = "backend-boringssl", feature = "backend-ring"),
+    all(feature = "backend-openssl", feature = "backend-ring"),
+))]
+compile_error!(
+    "multiple cryptographic backends selected for Soter, only one is allowed \
+     (note that backend-boringssl is enabled by default, use \"default-features = false\")"
+);
+
+#[cfg(all(
+    feature = "fips",
+    any(
+        feature = "backend-aws-lc",
+        feature = "backend-openssl",
+        feature = "backend-ring",
+    )
+))]
+compile_error!("\"fips\" feature is supported only with BoringSSL backend");
+
+// Backends are native libraries which are not built for WebAssembly.
+#[cfg(all(
+    target_arch = "wasm32",
+    any(
+        feature = "backend-aws-lc",
+        feature = "backend-boringssl",
+        feature = "backend-openssl",
+        feature = "backend-ring",
+    )
+))]
+compile_error!(
+    "cryptographic backends are not available for WebAssembly, \
+     build Soter with \"default-features = false\""
+);
+
+#[cfg(all(feature = "backend-aws-lc", not(target_arch = "wasm32")))]
+use aws_lc as backend;
+#[cfg(all(feature = "backend-boringssl", not(target_arch = "wasm32")))]
+use boringssl as backend;
+#[cfg(all(feature = "backend-openssl", not(target_arch = "wasm32")))]
+use openssl as backend;
+#[cfg(all(feature = "backend-ring", not(target_arch = "wasm32")))]
+use ring as backend;
 
 pub use error::{Error, ErrorKind, Result};
+#[cfg(feature = "std")]
+pub use info::{backend_info, BackendInfo, Capability};
+pub use self_test::{self_test, SelfTestReport, TestOutcome};
+
+/// Returns `true` if the cryptographic backend is running in FIPS mode.
+///
+/// Build Soter with `fips` feature to use BoringSSL FIPS module. The module is
+/// required to perform self-tests on startup, if they fail then it is not
+/// in FIPS mode. Some other backends can be configured for FIPS mode externally.
+///
+/// With `fips` feature Soter also refuses to use algorithms which are not
+/// approved by FIPS 140, returning [`NotSupported`] errors instead.
+///
+/// [`NotSupported`]: enum.ErrorKind.html#variant.NotSupported
+#[cfg(feature = "std")]
+pub fn is_fips() -> bool {
+    backend::FIPS_mode()
+}
@@ -0,0 +1,465 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory for secrets.
+//!
+//! Secret data such as private keys should not stay around in memory longer than
+//! necessary. [`SecureBuffer`] provides storage which is wiped on drop and takes
+//! additional measures to avoid leaking its contents.
+//!
+//! Secrets should not leak into logs either. [`Redacted`] is a placeholder used
+//! in `Debug` output of all types holding secrets.
+//!
+//! [`SecureBuffer`]: struct.SecureBuffer.html
+//! [`Redacted`]: struct.Redacted.html
+
+use alloc::boxed::Box;
+use alloc::vec;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use zeroize::Zeroize;
+
+/// Buffer for secret data.
+///
+/// `SecureBuffer` is a fixed-size byte buffer which protects its contents from leaking
+/// in the ways a regular heap allocation might:
+///
+/// - the memory is locked into RAM, so that it is never swapped out to disk
+///   (with `mlock()` on Unix and `VirtualLock()` on Windows);
+/// - on Linux, the memory is excluded from core dumps;
+/// - the buffer is surrounded by inaccessible guard pages, so that overruns crash
+///   the process instead of reading or overwriting neighbouring data;
+/// - the contents are wiped when the buffer is dropped.
+///
+/// All of these are best-effort. The system may refuse to lock memory, for example,
+/// due to `RLIMIT_MEMLOCK` limit, in which case the buffer still works but may be swapped
+/// out. Use [`is_locked`] to check. On platforms without virtual memory management
+/// (like WebAssembly) the buffer is allocated on the heap and is only wiped on drop.
+///
+/// Every buffer occupies at least three pages of virtual memory, and locked memory is
+/// a limited resource. Use `SecureBuffer` for long-lived secrets like private keys,
+/// not for every temporary value.
+///
+/// [`is_locked`]: struct.SecureBuffer.html#method.is_locked
+///
+/// # Examples
+///
+/// ```
+/// use soter::memory::SecureBuffer;
+///
+/// let mut key = SecureBuffer::new(32);
+/// # #[cfg(feature = "std")]
+/// soter::rand::bytes(&mut key);
+/// assert_eq!(key.len(), 32);
+/// ```
+pub struct SecureBuffer {
+    storage: Storage,
+}
+
+enum Storage {
+    Protected(sys::Region),
+    Heap(Box<[u8]>),
+}
+
+impl SecureBuffer {
+    /// Allocates a new zero-filled buffer of given length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory cannot be allocated.
+    pub fn new(len: usize) -> SecureBuffer {
+        let storage = if len == 0 {
+            Storage::Heap(Box::new([]))
+        } else {
+            match sys::Region::allocate(len) {
+                Some(region) => Storage::Protected(region),
+                None => Storage::Heap(vec![0; len].into_boxed_slice()),
+            }
+        };
+        SecureBuffer { storage }
+    }
+
+    /// Allocates a new buffer with a copy of given data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory cannot be allocated.
+    pub fn from_slice(data: &[u8]) -> SecureBuffer {
+        let mut buffer = SecureBuffer::new(data.len());
+        buffer.copy_from_slice(data);
+        buffer
+    }
+
+    /// Returns `true` if the buffer is locked in memory and will not be swapped out.
+    ///
+    /// Empty buffers are never locked.
+    pub fn is_locked(&self) -> bool {
+        match &self.storage {
+            Storage::Protected(region) => region.is_locked(),
+            Storage::Heap(_) => false,
+        }
+    }
+}
+
+impl Deref for SecureBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.storage {
+            Storage::Protected(region) => region.as_slice(),
+            Storage::Heap(bytes) => bytes,
+        }
+    }
+}
+
+impl DerefMut for SecureBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match &mut self.storage {
+            Storage::Protected(region) => region.as_mut_slice(),
+            Storage::Heap(bytes) => bytes,
+        }
+    }
+}
+
+impl AsRef<[u8]> for SecureBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for SecureBuffer {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl Clone for SecureBuffer {
+    fn clone(&self) -> SecureBuffer {
+        SecureBuffer::from_slice(self)
+    }
+}
+
+impl fmt::Debug for SecureBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SecureBuffer")
+            .field(&Redacted::new(self))
+            .finish()
+    }
+}
+
+/// Placeholder for secret data in `Debug` output.
+///
+/// Types holding secrets should never print them out, not even partially,
+/// since `Debug` output often ends up in logs and panic messages. Use `Redacted`
+/// in `Debug` implementations instead of the secret. It shows only the length of the data.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt;
+///
+/// use soter::memory::Redacted;
+///
+/// struct Password(String);
+///
+/// impl fmt::Debug for Password {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.debug_tuple("Password")
+///             .field(&Redacted::new(self.0.as_bytes()))
+///             .finish()
+///     }
+/// }
+///
+/// let password = Password("hunter2".to_string());
+/// assert_eq!(format!("{:?}", password), "Password(<redacted 7 bytes>)");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Redacted {
+    len: usize,
+}
+
+impl Redacted {
+    /// Makes a placeholder for given secret data.
+    pub fn new(secret: &[u8]) -> Redacted {
+        Redacted { len: secret.len() }
+    }
+}
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<redacted {} bytes>", self.len)
+    }
+}
+
+impl Drop for SecureBuffer {
+    fn drop(&mut self) {
+        self.deref_mut().zeroize();
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use core::ptr;
+    use core::slice;
+
+    use libc::c_void;
+
+    /// Memory mapping with locked data pages surrounded by guard pages.
+    pub(super) struct Region {
+        base: *mut u8,
+        size: usize,
+        data: *mut u8,
+        len: usize,
+        locked: bool,
+    }
+
+    // Region owns its memory exclusively, just like a Box.
+    unsafe impl Send for Region {}
+    unsafe impl Sync for Region {}
+
+    impl Region {
+        pub(super) fn allocate(len: usize) -> Option<Region> {
+            let page = page_size();
+            let data_size = len.checked_add(page - 1)? / page * page;
+            let size = data_size.checked_add(2 * page)?;
+            let base = unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    size,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANON,
+                    -1,
+                    0,
+                )
+            };
+            if base == libc::MAP_FAILED {
+                return None;
+            }
+            let base = base as *mut u8;
+            let pages = unsafe { base.add(page) };
+            let mut region = Region {
+                base,
+                size,
+                // Place data at the end of the pages so that overruns hit the guard page.
+                data: unsafe { pages.add(data_size - len) },
+                len,
+                locked: false,
+            };
+            unsafe {
+                let tail = pages.add(data_size);
+                if libc::mprotect(base as *mut c_void, page, libc::PROT_NONE) != 0
+                    || libc::mprotect(tail as *mut c_void, page, libc::PROT_NONE) != 0
+                {
+                    return None;
+                }
+                region.locked = libc::mlock(pages as *const c_void, data_size) == 0;
+                #[cfg(any(target_os = "linux", target_os = "android"))]
+                libc::madvise(pages as *mut c_void, data_size, libc::MADV_DONTDUMP);
+            }
+            Some(region)
+        }
+
+        pub(super) fn is_locked(&self) -> bool {
+            self.locked
+        }
+
+        pub(super) fn as_slice(&self) -> &[u8] {
+            unsafe { slice::from_raw_parts(self.data, self.len) }
+        }
+
+        pub(super) fn as_mut_slice(&mut self) -> &mut [u8] {
+            unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+        }
+    }
+
+    impl Drop for Region {
+        fn drop(&mut self) {
+            let page = page_size();
+            unsafe {
+                if self.locked {
+                    libc::munlock(self.base.add(page) as *const c_void, self.size - 2 * page);
+                }
+                libc::munmap(self.base as *mut c_void, self.size);
+            }
+        }
+    }
+
+    fn page_size() -> usize {
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use core::mem;
+    use core::ptr;
+    use core::slice;
+
+    use winapi::um::memoryapi::{
+        VirtualAlloc, VirtualFree, VirtualLock, VirtualProtect, VirtualUnlock,
+    };
+    use winapi::um::sysinfoapi::{GetSystemInfo, SYSTEM_INFO};
+    use winapi::um::winnt::{MEM_COMMIT, MEM_RELEASE, MEM_RESERVE, PAGE_NOACCESS, PAGE_READWRITE};
+
+    /// Virtual memory allocation with locked data pages surrounded by guard pages.
+    pub(super) struct Region {
+        base: *mut u8,
+        size: usize,
+        data: *mut u8,
+        len: usize,
+        locked: bool,
+    }
+
+    // Region owns its memory exclusively, just like a Box.
+    unsafe impl Send for Region {}
+    unsafe impl Sync for Region {}
+
+    impl Region {
+        pub(super) fn allocate(len: usize) -> Option<Region> {
+            let page = page_size();
+            let data_size = len.checked_add(page - 1)? / page * page;
+            let size = data_size.checked_add(2 * page)?;
+            let base = unsafe {
+                VirtualAlloc(
+                    ptr::null_mut(),
+                    size,
+                    MEM_RESERVE | MEM_COMMIT,
+                    PAGE_READWRITE,
+                )
+            };
+            if base.is_null() {
+                return None;
+            }
+            let base = base as *mut u8;
+            let pages = unsafe { base.add(page) };
+            let mut region = Region {
+                base,
+                size,
+                // Place data at the end of the pages so that overruns hit the guard page.
+                data: unsafe { pages.add(data_size - len) },
+                len,
+                locked: false,
+            };
+            unsafe {
+                let tail = pages.add(data_size);
+                let mut old_protection = 0;
+                if VirtualProtect(base as _, page, PAGE_NOACCESS, &mut old_protection) == 0
+                    || VirtualProtect(tail as _, page, PAGE_NOACCESS, &mut old_protection) == 0
+                {
+                    return None;
+                }
+                region.locked = VirtualLock(pages as _, data_size) != 0;
+            }
+            Some(region)
+        }
+
+        pub(super) fn is_locked(&self) -> bool {
+            self.locked
+        }
+
+        pub(super) fn as_slice(&self) -> &[u8] {
+            unsafe { slice::from_raw_parts(self.data, self.len) }
+        }
+
+        pub(super) fn as_mut_slice(&mut self) -> &mut [u8] {
+            unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+        }
+    }
+
+    impl Drop for Region {
+        fn drop(&mut self) {
+            let page = page_size();
+            unsafe {
+                if self.locked {
+                    VirtualUnlock(self.base.add(page) as _, self.size - 2 * page);
+                }
+                VirtualFree(self.base as _, 0, MEM_RELEASE);
+            }
+        }
+    }
+
+    fn page_size() -> usize {
+        unsafe {
+            let mut info: SYSTEM_INFO = mem::zeroed();
+            GetSystemInfo(&mut info);
+            info.dwPageSize as usize
+        }
+    }
+}
+
+// Other platforms (like WebAssembly) do not have virtual memory protection,
+// use regular heap allocations there.
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    pub(super) enum Region {}
+
+    impl Region {
+        pub(super) fn allocate(_len: usize) -> Option<Region> {
+            None
+        }
+
+        pub(super) fn is_locked(&self) -> bool {
+            match *self {}
+        }
+
+        pub(super) fn as_slice(&self) -> &[u8] {
+            match *self {}
+        }
+
+        pub(super) fn as_mut_slice(&mut self) -> &mut [u8] {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_filled() {
+        for &len in &[1, 32, 4095, 4096, 4097, 10000] {
+            let buffer = SecureBuffer::new(len);
+            assert_eq!(buffer.len(), len);
+            assert!(buffer.iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let buffer = SecureBuffer::new(0);
+        assert!(buffer.is_empty());
+        assert!(!buffer.is_locked());
+    }
+
+    #[test]
+    fn read_write() {
+        let mut buffer = SecureBuffer::from_slice(b"secret");
+        assert_eq!(&buffer[..], b"secret");
+        buffer[0] = b'S';
+        assert_eq!(&buffer[..], b"Secret");
+
+        let copy = buffer.clone();
+        buffer[1] = b'E';
+        assert_eq!(&copy[..], b"Secret");
+        assert_eq!(&buffer[..], b"SEcret");
+    }
+
+    #[test]
+    fn debug_redacted() {
+        let buffer = SecureBuffer::from_slice(b"secret");
+        assert_eq!(format!("{:?}", buffer), "SecureBuffer(<redacted 6 bytes>)");
+    }
+}
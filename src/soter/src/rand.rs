@@ -14,6 +14,8 @@
 
 //! Generating random data.
 
+use crate::error::{Error, ErrorKind, Result};
+
 /// Generates pseudo-random bytes.
 ///
 /// This functions generates cryptographically strong pseudo-random bytes and fills
@@ -26,7 +28,10 @@
 /// other than having being started after the system entropy pool had been properly seeded.
 ///
 /// Note that some cryptographic backends might instead directly abort the process in this
-/// case, so you really should not try to ‘handle’ this failure.
+/// case, so you really should not try to ‘handle’ this failure. Still, if unwinding is not
+/// acceptable (e.g., at FFI boundaries), use [`try_bytes`] which returns an error instead.
+///
+/// [`try_bytes`]: fn.try_bytes.html
 ///
 /// # Example
 ///
@@ -41,10 +46,26 @@
 /// # }
 /// ```
 pub fn bytes(buffer: &mut [u8]) {
-    if let Err(error) = boringssl::RAND_bytes(buffer) {
+    if let Err(error) = try_bytes(buffer) {
         // Normally, BoringSSL will abort on failure, but double-tap just in case.
         // One possible case is that the system does not have a CSPRNG available,
         // which is equally fatal for the application.
-        panic!(format!("failed to generate random bytes: {}", error))
+        panic!("failed to generate random bytes: {}", error)
     }
 }
+
+/// Generates pseudo-random bytes, reporting failures.
+///
+/// This is the same as [`bytes`], but returns an error instead of panicking.
+///
+/// # Errors
+///
+/// Returns an error of [`RandomFailure`] kind if the system does not have enough entropy
+/// to fill the entire buffer with random data.
+///
+/// [`bytes`]: fn.bytes.html
+/// [`RandomFailure`]: ../enum.ErrorKind.html#variant.RandomFailure
+pub fn try_bytes(buffer: &mut [u8]) -> Result<()> {
+    crate::backend::RAND_bytes(buffer)
+        .map_err(|e| Error::from(e).with_kind(ErrorKind::RandomFailure))
+}
@@ -0,0 +1,244 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RSA cryptography.
+
+use std::fmt;
+
+#[cfg(feature = "rsa")]
+use crate::backend::{
+    BN_bn2bin_padded, RSA_generate_key_ex, RSA_get0_crt_params, RSA_get0_factors, RSA_get0_key,
+    RSA_new, BIGNUM,
+};
+use zeroize::Zeroize;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::memory::Redacted;
+
+/// Public exponent used for new keys: 65537.
+pub const PUBLIC_EXPONENT: u32 = 0x10001;
+
+/// RSA key pair.
+///
+/// All key components are available as big-endian integers, padded with zeros to fixed size.
+/// The modulus and the private exponent have the size of the key. The primes and CRT
+/// parameters have half of that size.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> soter::Result<()> {
+/// use soter::rsa::KeyPair;
+/// # if cfg!(feature = "backend-ring") || !cfg!(feature = "rsa") { return Ok(()); }
+///
+/// let key_pair = KeyPair::generate(2048)?;
+///
+/// assert_eq!(key_pair.modulus().len(), 256);
+/// assert_eq!(key_pair.public_exponent(), 65537);
+/// # Ok(())
+/// # }
+/// ```
+pub struct KeyPair {
+    public_exponent: u32,
+    modulus: Vec<u8>,
+    // Private exponent, p, q, dp, dq, qp -- in that order.
+    private: Vec<u8>,
+}
+
+impl KeyPair {
+    /// Generates a new random key pair with modulus of given size in bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`InvalidParameter`] kind if the key size is not a multiple of 16.
+    /// Small keys might be not supported by the backend.
+    ///
+    /// Returns an error of [`NotSupported`] kind if the backend cannot generate RSA keys
+    /// (e.g., ring), if Soter is built without `rsa` feature, or if the key is shorter
+    /// than 2048 bits in FIPS mode.
+    ///
+    /// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+    /// [`NotSupported`]: ../enum.ErrorKind.html#variant.NotSupported
+    pub fn generate(bits: usize) -> Result<KeyPair> {
+        if bits == 0 || bits & 0xF != 0 {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        // FIPS 186-4 allows generating only 2048-bit keys and larger.
+        if cfg!(feature = "fips") && bits < 2048 {
+            return Err(Error::new(ErrorKind::NotSupported));
+        }
+        // RSA implementation is linked in only with "rsa" feature.
+        generate_key_pair(bits)
+    }
+
+    /// Returns public exponent.
+    pub fn public_exponent(&self) -> u32 {
+        self.public_exponent
+    }
+
+    /// Returns modulus.
+    pub fn modulus(&self) -> &[u8] {
+        &self.modulus
+    }
+
+    /// Returns private exponent.
+    pub fn private_exponent(&self) -> &[u8] {
+        &self.private[..self.modulus.len()]
+    }
+
+    /// Returns the first prime factor.
+    pub fn p(&self) -> &[u8] {
+        self.private_component(0)
+    }
+
+    /// Returns the second prime factor.
+    pub fn q(&self) -> &[u8] {
+        self.private_component(1)
+    }
+
+    /// Returns the first CRT exponent: d mod (p - 1).
+    pub fn dp(&self) -> &[u8] {
+        self.private_component(2)
+    }
+
+    /// Returns the second CRT exponent: d mod (q - 1).
+    pub fn dq(&self) -> &[u8] {
+        self.private_component(3)
+    }
+
+    /// Returns CRT coefficient: q^(-1) mod p.
+    pub fn qp(&self) -> &[u8] {
+        self.private_component(4)
+    }
+
+    fn private_component(&self, index: usize) -> &[u8] {
+        let size = self.modulus.len();
+        let half = size / 2;
+        let start = size + index * half;
+        &self.private[start..start + half]
+    }
+}
+
+impl Drop for KeyPair {
+    fn drop(&mut self) {
+        self.private.zeroize();
+    }
+}
+
+// Never print out private components.
+impl fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("public_exponent", &self.public_exponent)
+            .field("modulus", &self.modulus)
+            .field("private", &Redacted::new(&self.private))
+            .finish()
+    }
+}
+
+#[cfg(feature = "rsa")]
+fn generate_key_pair(bits: usize) -> Result<KeyPair> {
+    let mut rsa = RSA_new()?;
+    RSA_generate_key_ex(&mut rsa, bits, PUBLIC_EXPONENT)?;
+
+    let size = bits / 8;
+    let half = size / 2;
+    let (n, e, d) = RSA_get0_key(&rsa);
+    let (p, q) = RSA_get0_factors(&rsa);
+    let (dp, dq, qp) = RSA_get0_crt_params(&rsa);
+
+    let mut key_pair = KeyPair {
+        public_exponent: 0,
+        modulus: vec![0; size],
+        private: vec![0; size + 5 * half],
+    };
+
+    let mut public_exponent = [0; 4];
+    export(&mut public_exponent, e)?;
+    key_pair.public_exponent = u32::from_be_bytes(public_exponent);
+    export(&mut key_pair.modulus, n)?;
+
+    let (private_exponent, rest) = key_pair.private.split_at_mut(size);
+    export(private_exponent, d)?;
+    for (buffer, component) in rest.chunks_mut(half).zip(vec![p, q, dp, dq, qp]) {
+        export(buffer, component)?;
+    }
+
+    Ok(key_pair)
+}
+
+#[cfg(not(feature = "rsa"))]
+fn generate_key_pair(_bits: usize) -> Result<KeyPair> {
+    Err(Error::new(ErrorKind::NotSupported))
+}
+
+#[cfg(feature = "rsa")]
+fn export(buffer: &mut [u8], value: Option<BIGNUM>) -> Result<()> {
+    let value = value.ok_or(Error::new(ErrorKind::Failure))?;
+    BN_bn2bin_padded(buffer, &value)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(any(feature = "backend-ring", not(feature = "rsa")), ignore)]
+    fn generate_2048() {
+        let key_pair = KeyPair::generate(2048).expect("key generation");
+        assert_eq!(key_pair.public_exponent(), PUBLIC_EXPONENT);
+        assert_eq!(key_pair.modulus().len(), 256);
+        assert_eq!(key_pair.private_exponent().len(), 256);
+        for component in &[
+            key_pair.p(),
+            key_pair.q(),
+            key_pair.dp(),
+            key_pair.dq(),
+            key_pair.qp(),
+        ] {
+            assert_eq!(component.len(), 128);
+        }
+        // The modulus has exactly 2048 bits.
+        assert!(key_pair.modulus()[0] & 0x80 != 0);
+    }
+
+    #[test]
+    #[cfg(any(feature = "backend-ring", not(feature = "rsa")))]
+    fn generate_not_supported() {
+        let err = KeyPair::generate(2048)
+            .map(|_| ())
+            .expect_err("RSA key generation is not available");
+        assert_eq!(err.kind(), ErrorKind::NotSupported);
+    }
+
+    #[test]
+    #[cfg(feature = "fips")]
+    fn fips_small_keys() {
+        let err = KeyPair::generate(1024)
+            .map(|_| ())
+            .expect_err("1024-bit keys are not approved");
+        assert_eq!(err.kind(), ErrorKind::NotSupported);
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        for &bits in &[0, 1000, 2047] {
+            let err = KeyPair::generate(bits)
+                .map(|_| ())
+                .expect_err("invalid size");
+            assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+        }
+    }
+}
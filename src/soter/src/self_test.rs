@@ -0,0 +1,300 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Known-answer self-tests.
+
+use alloc::vec::Vec;
+
+use crate::crc::platform::software;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::crc::platform::{sse42, vpclmulqdq};
+use crate::crc::INIT_CRC32;
+use crate::encoding;
+
+/// Runs known-answer tests for all available primitives.
+///
+/// Each primitive is run on fixed inputs and its output is compared with the expected one.
+/// This detects broken or miscompiled implementations, including platform-specific
+/// ones: all SIMD variants supported by the processor are tested, not only the fastest.
+/// Deployments which must verify cryptographic health at startup (e.g., for FIPS 140)
+/// can call this function and refuse to proceed if the report indicates a failure.
+///
+/// Primitives provided by the cryptographic backend are tested only with `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// let report = soter::self_test();
+///
+/// if !report.passed() {
+///     for test in report.failures() {
+///         eprintln!("self-test failed: {}", test.name());
+///     }
+///     panic!("Soter is broken");
+/// }
+/// ```
+pub fn self_test() -> SelfTestReport {
+    let mut report = SelfTestReport {
+        outcomes: Vec::new(),
+    };
+    for &(name, test) in KNOWN_ANSWER_TESTS {
+        if let Some(passed) = run(test) {
+            report.outcomes.push(TestOutcome { name, passed });
+        }
+    }
+    report
+}
+
+/// Results of [`self_test`].
+///
+/// [`self_test`]: fn.self_test.html
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    outcomes: Vec<TestOutcome>,
+}
+
+impl SelfTestReport {
+    /// Returns `true` if all tests have passed.
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.passed)
+    }
+
+    /// Returns outcomes of all tests which have been run.
+    pub fn outcomes(&self) -> &[TestOutcome] {
+        &self.outcomes
+    }
+
+    /// Returns outcomes of failed tests.
+    pub fn failures(&self) -> impl Iterator<Item = &TestOutcome> + '_ {
+        self.outcomes.iter().filter(|outcome| !outcome.passed)
+    }
+}
+
+/// Outcome of a single known-answer test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestOutcome {
+    name: &'static str,
+    passed: bool,
+}
+
+impl TestOutcome {
+    /// Returns name of the tested primitive, e.g., "SHA-256".
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns `true` if the primitive produced expected results.
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+}
+
+/// Known-answer test. Returns `None` if the primitive is not available.
+type KnownAnswerTest = fn() -> Option<bool>;
+
+// Known-answer tests use fallible APIs, but still catch panics where unwinding is available
+// in case some primitive panics anyway, reporting that as a failure.
+#[cfg(feature = "std")]
+fn run(test: KnownAnswerTest) -> Option<bool> {
+    std::panic::catch_unwind(test).unwrap_or(Some(false))
+}
+
+#[cfg(not(feature = "std"))]
+fn run(test: KnownAnswerTest) -> Option<bool> {
+    test()
+}
+
+const KNOWN_ANSWER_TESTS: &[(&str, KnownAnswerTest)] = &[
+    ("CRC-32C", crc32c_software),
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    ("CRC-32C (SSE 4.2)", crc32c_sse42),
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    ("CRC-32C (VPCLMULQDQ)", crc32c_vpclmulqdq),
+    ("Base64", base64),
+    ("hex", hex),
+    #[cfg(feature = "std")]
+    ("SHA-256", backend::sha256),
+    #[cfg(feature = "std")]
+    ("SHA-512", backend::sha512),
+    #[cfg(feature = "std")]
+    ("SM3", backend::sm3),
+    #[cfg(feature = "std")]
+    ("HMAC-SHA-256", backend::hmac_sha256),
+    #[cfg(feature = "std")]
+    ("HMAC-SHA-512", backend::hmac_sha512),
+    #[cfg(feature = "std")]
+    ("Soter KDF", backend::kdf),
+];
+
+// CRC-32C check value from the CRC catalogue, and a longer input for vectorized code.
+const CRC32C_VECTORS: &[(&[u8], u32)] = &[(b"123456789", 0xE306_9283), (&CRC32C_LONG, 0xA5E5_B4B5)];
+
+const CRC32C_LONG: [u8; 1024] = {
+    let mut data = [0; 1024];
+    let mut i = 0;
+    while i < data.len() {
+        data[i] = (i * 31 + 7) as u8;
+        i += 1;
+    }
+    data
+};
+
+fn check_crc32c(update: impl Fn(u32, &[u8]) -> u32) -> bool {
+    CRC32C_VECTORS
+        .iter()
+        .all(|&(data, expected)| !update(INIT_CRC32, data) == expected)
+}
+
+fn crc32c_software() -> Option<bool> {
+    Some(check_crc32c(software::update_crc32c))
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn crc32c_sse42() -> Option<bool> {
+    if !x86_feature_detected!("sse4.2") {
+        return None;
+    }
+    // We have checked for SSE 4.2 availability, it is safe to proceed.
+    Some(check_crc32c(|state, data| unsafe {
+        sse42::update_crc32c(state, data)
+    }))
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn crc32c_vpclmulqdq() -> Option<bool> {
+    if !x86_feature_detected!("avx512f", "vpclmulqdq", "sse4.2") {
+        return None;
+    }
+    // We have checked for AVX-512 availability, it is safe to proceed.
+    Some(check_crc32c(|state, data| unsafe {
+        vpclmulqdq::update_crc32c(state, data)
+    }))
+}
+
+fn base64() -> Option<bool> {
+    // Long enough for vectorized code.
+    let data: Vec<u8> = (0..48).collect();
+    let expected = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v";
+    let encoded = encoding::base64_encode(&data);
+    let decoded = encoding::base64_decode(expected);
+    Some(encoded == expected && decoded.ok() == Some(data))
+}
+
+fn hex() -> Option<bool> {
+    let encoded = encoding::hex_encode(b"foobar");
+    let decoded = encoding::hex_decode("666F6F626172");
+    Some(encoded == "666f6f626172" && decoded.ok().as_deref() == Some(&b"foobar"[..]))
+}
+
+#[cfg(feature = "std")]
+mod backend {
+    use crate::encoding::hex_encode;
+    use crate::error::Result;
+    use crate::hash::{Algorithm, Hash};
+    use crate::hmac::Hmac;
+    use crate::kdf;
+
+    // NIST test vectors: https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const SHA512_ABC: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+    // GB/T 32905-2016, appendix A.1
+    const SM3_ABC: &str = "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0";
+
+    // RFC 4231, test case 2
+    const HMAC_KEY: &str = "Jefe";
+    const HMAC_DATA: &str = "what do ya want for nothing?";
+    const HMAC_SHA256: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+    const HMAC_SHA512: &str = "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737";
+
+    // Compatible with soter_kdf() of C Themis.
+    const KDF_OUTPUT: &str = "19bdc672b44386a16d24fc66994d9ef8accf1ed1caa159904bac6d59107f7669";
+
+    // Use fallible methods: with "panic = abort" a backend fault must not take the process down.
+
+    fn hash(algorithm: Algorithm, expected: &str) -> Option<bool> {
+        let compute = || -> Result<Vec<u8>> {
+            let mut hash = Hash::try_new(algorithm)?;
+            hash.try_write("abc")?;
+            hash.try_get()
+        };
+        Some(compute().is_ok_and(|digest| hex_encode(digest) == expected))
+    }
+
+    fn hmac(algorithm: Algorithm, expected: &str) -> Option<bool> {
+        let compute = || -> Result<Vec<u8>> {
+            let mut hmac = Hmac::try_new(algorithm, HMAC_KEY)?;
+            hmac.try_write(HMAC_DATA)?;
+            hmac.try_get()
+        };
+        Some(compute().is_ok_and(|mac| hex_encode(mac) == expected))
+    }
+
+    pub(super) fn sha256() -> Option<bool> {
+        hash(Algorithm::SHA256, SHA256_ABC)
+    }
+
+    pub(super) fn sha512() -> Option<bool> {
+        hash(Algorithm::SHA512, SHA512_ABC)
+    }
+
+    pub(super) fn sm3() -> Option<bool> {
+        // SM3 is not approved by FIPS 140 and is not available then.
+        if cfg!(feature = "fips") {
+            return None;
+        }
+        hash(Algorithm::SM3, SM3_ABC)
+    }
+
+    pub(super) fn hmac_sha256() -> Option<bool> {
+        hmac(Algorithm::SHA256, HMAC_SHA256)
+    }
+
+    pub(super) fn hmac_sha512() -> Option<bool> {
+        hmac(Algorithm::SHA512, HMAC_SHA512)
+    }
+
+    pub(super) fn kdf() -> Option<bool> {
+        let mut output = [0; 32];
+        let result = kdf::derive(Some(b"key"), "label", &[b"context"], &mut output);
+        Some(result.is_ok() && hex_encode(output) == KDF_OUTPUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_tests_pass() {
+        let report = self_test();
+        assert!(report.passed(), "{:?}", report);
+        assert_eq!(report.failures().count(), 0);
+    }
+
+    #[test]
+    fn software_tests_are_run() {
+        let report = self_test();
+        let names: Vec<&str> = report.outcomes().iter().map(|o| o.name()).collect();
+        assert!(names.contains(&"CRC-32C"));
+        assert!(names.contains(&"Base64"));
+        #[cfg(feature = "std")]
+        assert!(names.contains(&"SHA-256"));
+    }
+
+    #[test]
+    fn corrupted_crc_is_detected() {
+        let broken = |state: u32, data: &[u8]| software::update_crc32c(state ^ 1, data);
+        assert!(!check_crc32c(broken));
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shamir's secret sharing.
+//!
+//! A secret is split into _n_ shares so that any _k_ of them are enough to recover it,
+//! while fewer than _k_ shares reveal nothing about the secret. Each byte of the secret
+//! is shared independently using a random polynomial of degree _k_ − 1 over GF(2^8).
+//!
+//! Field arithmetic is implemented without secret-dependent branches or table lookups.
+//!
+//! Shares are encoded as their x-coordinate (a non-zero byte) followed by the values
+//! of polynomials at that point, one byte for each byte of the secret.
+
+use std::fmt;
+
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::memory::Redacted;
+
+/// A share of a secret.
+pub struct Share {
+    bytes: Vec<u8>,
+}
+
+impl Share {
+    /// Parses a share from its encoded form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`MalformedData`] kind if the data is not a valid share.
+    ///
+    /// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+    pub fn try_from_slice(bytes: impl AsRef<[u8]>) -> Result<Share> {
+        let bytes = bytes.as_ref();
+        if bytes.len() < 2 || bytes[0] == 0 {
+            return Err(Error::new(ErrorKind::MalformedData));
+        }
+        Ok(Share {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Returns the index of this share, from 1 to 255.
+    pub fn index(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    /// Returns encoded share.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn values(&self) -> &[u8] {
+        &self.bytes[1..]
+    }
+}
+
+impl Clone for Share {
+    fn clone(&self) -> Share {
+        Share {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+// Never print out share data.
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Share")
+            .field(&self.index())
+            .field(&Redacted::new(self.values()))
+            .finish()
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+/// Splits a secret into `count` shares, `threshold` of which are needed to recover it.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if the secret is empty, if `threshold`
+/// is less than 2, or if `count` is less than `threshold`.
+///
+/// Returns an error of [`RandomFailure`] kind if random coefficients cannot be generated.
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+/// [`RandomFailure`]: ../enum.ErrorKind.html#variant.RandomFailure
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> soter::Result<()> {
+/// use soter::shamir;
+///
+/// let shares = shamir::split(b"secret", 3, 5)?;
+///
+/// let secret = shamir::combine(&shares[1..4])?;
+/// assert_eq!(secret, b"secret");
+/// # Ok(())
+/// # }
+/// ```
+pub fn split(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Share>> {
+    if secret.is_empty() || threshold < 2 || count < threshold {
+        return Err(Error::new(ErrorKind::InvalidParameter));
+    }
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|x| {
+            let mut bytes = vec![0; 1 + secret.len()];
+            bytes[0] = x;
+            Share { bytes }
+        })
+        .collect();
+
+    // Coefficients of the polynomial, from the highest degree. The constant term is
+    // the secret byte itself.
+    let mut coefficients = Zeroizing::new(vec![0; threshold as usize - 1]);
+    for (i, &secret_byte) in secret.iter().enumerate() {
+        crate::rand::try_bytes(&mut coefficients)?;
+        for share in shares.iter_mut() {
+            let x = share.bytes[0];
+            let mut y = 0;
+            for &coefficient in coefficients.iter() {
+                y = gf_mul(y, x) ^ coefficient;
+            }
+            share.bytes[1 + i] = gf_mul(y, x) ^ secret_byte;
+        }
+    }
+
+    Ok(shares)
+}
+
+/// Recovers a secret from its shares.
+///
+/// Note that there is no way to tell whether enough shares have been provided.
+/// If there are fewer shares than the threshold used for splitting then the result
+/// is some unrelated data.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if there are no shares, if shares
+/// have different lengths, or if there are duplicate shares.
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>> {
+    let length = match shares.first() {
+        Some(share) => share.values().len(),
+        None => return Err(Error::new(ErrorKind::InvalidParameter)),
+    };
+    for (i, share) in shares.iter().enumerate() {
+        if share.values().len() != length {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        if shares[..i]
+            .iter()
+            .any(|other| other.index() == share.index())
+        {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+    }
+
+    // Lagrange interpolation at zero: secret = sum of y_i * l_i, where
+    // l_i = product of x_j / (x_j - x_i) for all j != i.
+    let mut secret = vec![0; length];
+    for share in shares {
+        let mut numerator = 1;
+        let mut denominator = 1;
+        for other in shares {
+            if other.index() != share.index() {
+                numerator = gf_mul(numerator, other.index());
+                denominator = gf_mul(denominator, other.index() ^ share.index());
+            }
+        }
+        let basis = gf_mul(numerator, gf_inv(denominator));
+        for (s, &y) in secret.iter_mut().zip(share.values()) {
+            *s ^= gf_mul(y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0;
+    for _ in 0..8 {
+        result ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1B & carry);
+        b >>= 1;
+    }
+    result
+}
+
+/// Multiplicative inverse in GF(2^8), computed as a^254. Zero maps to zero.
+fn gf_inv(a: u8) -> u8 {
+    // 254 = 0b11111110
+    let a2 = gf_mul(a, a);
+    let a4 = gf_mul(a2, a2);
+    let a8 = gf_mul(a4, a4);
+    let a16 = gf_mul(a8, a8);
+    let a32 = gf_mul(a16, a16);
+    let a64 = gf_mul(a32, a32);
+    let a128 = gf_mul(a64, a64);
+    gf_mul(
+        gf_mul(gf_mul(a2, a4), gf_mul(a8, a16)),
+        gf_mul(gf_mul(a32, a64), a128),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_arithmetic() {
+        // Example from FIPS 197, section 4.2.
+        assert_eq!(gf_mul(0x57, 0x83), 0xC1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xFE);
+        assert_eq!(gf_inv(0), 0);
+        for a in 1..=255 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn any_subset_works() {
+        let secret = b"correct horse battery staple";
+        let shares = split(secret, 3, 5).unwrap();
+        for i in 0..5 {
+            for j in 0..5 {
+                for k in 0..5 {
+                    if i == j || j == k || i == k {
+                        continue;
+                    }
+                    let subset = [shares[i].clone(), shares[j].clone(), shares[k].clone()];
+                    assert_eq!(combine(&subset).unwrap(), secret);
+                }
+            }
+        }
+        assert_eq!(combine(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn too_few_shares() {
+        let secret = [0x42; 32];
+        let shares = split(&secret, 3, 5).unwrap();
+        assert_ne!(combine(&shares[..2]).unwrap(), secret);
+    }
+
+    #[test]
+    fn shares_are_random() {
+        let shares_1 = split(b"secret", 2, 2).unwrap();
+        let shares_2 = split(b"secret", 2, 2).unwrap();
+        assert_ne!(shares_1[0].as_bytes(), shares_2[0].as_bytes());
+        assert_ne!(shares_1[0].values(), b"secret");
+    }
+
+    #[test]
+    fn encoding() {
+        let shares = split(b"secret", 2, 3).unwrap();
+        let parsed: Vec<Share> = shares
+            .iter()
+            .map(|share| Share::try_from_slice(share.as_bytes()).unwrap())
+            .collect();
+        assert_eq!(parsed[2].index(), 3);
+        assert_eq!(combine(&parsed[1..]).unwrap(), b"secret");
+
+        for bytes in [&[][..], &[1], &[0, 1, 2]] {
+            let err = Share::try_from_slice(bytes).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::MalformedData);
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert!(split(b"", 2, 3).is_err());
+        assert!(split(b"secret", 1, 3).is_err());
+        assert!(split(b"secret", 3, 2).is_err());
+        assert!(combine(&[]).is_err());
+
+        let shares = split(b"secret", 2, 3).unwrap();
+        let duplicate = [shares[0].clone(), shares[0].clone()];
+        assert_eq!(
+            combine(&duplicate).unwrap_err().kind(),
+            ErrorKind::InvalidParameter
+        );
+        let other = split(b"other secret", 2, 3).unwrap();
+        let mismatched = [shares[0].clone(), other[1].clone()];
+        assert_eq!(
+            combine(&mismatched).unwrap_err().kind(),
+            ErrorKind::InvalidParameter
+        );
+    }
+}
@@ -0,0 +1,178 @@
+/*
+ * Copyright 2020 themis.rs maintainers
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#ifndef THEMIS_THEMIS_H
+#define THEMIS_THEMIS_H
+
+/* Generated by cbindgen from themis-capi sources. Do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Kind of an asymmetric key.
+ */
+typedef enum {
+  /**
+   * Invalid key kind.
+   */
+  THEMIS_KEY_INVALID = 0,
+  /**
+   * RSA private key.
+   */
+  THEMIS_KEY_RSA_PRIVATE = 1,
+  /**
+   * RSA public key.
+   */
+  THEMIS_KEY_RSA_PUBLIC = 2,
+  /**
+   * ECDSA private key.
+   */
+  THEMIS_KEY_EC_PRIVATE = 3,
+  /**
+   * ECDSA public key.
+   */
+  THEMIS_KEY_EC_PUBLIC = 4,
+} themis_key_kind_t;
+
+/**
+ * Status code returned by Themis functions.
+ */
+typedef int32_t themis_status_t;
+
+/**
+ * Operation successful.
+ */
+#define THEMIS_SUCCESS 0
+
+/**
+ * General failure.
+ */
+#define THEMIS_FAIL 11
+
+/**
+ * Invalid parameter.
+ */
+#define THEMIS_INVALID_PARAMETER 12
+
+/**
+ * Memory allocation failure.
+ */
+#define THEMIS_NO_MEMORY 13
+
+/**
+ * Output buffer is too small.
+ */
+#define THEMIS_BUFFER_TOO_SMALL 14
+
+/**
+ * Input data is corrupted.
+ */
+#define THEMIS_DATA_CORRUPT 15
+
+/**
+ * Signature verification failed.
+ */
+#define THEMIS_INVALID_SIGNATURE 16
+
+/**
+ * Operation is not supported.
+ */
+#define THEMIS_NOT_SUPPORTED 17
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Generates an ECDSA key pair.
+ *
+ * If any of the buffers is `NULL` or too small, their lengths are updated with the sizes
+ * needed and `THEMIS_BUFFER_TOO_SMALL` is returned.
+ *
+ * # Safety
+ *
+ * Buffers must be valid for writes of the length indicated by length pointers.
+ */
+themis_status_t themis_gen_ec_key_pair(uint8_t *private_key,
+                                       size_t *private_key_length,
+                                       uint8_t *public_key,
+                                       size_t *public_key_length);
+
+/**
+ * Generates an RSA key pair.
+ *
+ * Keys are 2048 bits long. If any of the buffers is `NULL` or too small, their lengths
+ * are updated with the sizes needed and `THEMIS_BUFFER_TOO_SMALL` is returned.
+ *
+ * # Safety
+ *
+ * Buffers must be valid for writes of the length indicated by length pointers.
+ */
+themis_status_t themis_gen_rsa_key_pair(uint8_t *private_key,
+                                        size_t *private_key_length,
+                                        uint8_t *public_key,
+                                        size_t *public_key_length);
+
+/**
+ * Generates a symmetric key.
+ *
+ * If `key` is `NULL` or `key_length` is zero, `key_length` is updated with the default
+ * key length and `THEMIS_BUFFER_TOO_SMALL` is returned. Otherwise the buffer is filled
+ * with `key_length` random bytes.
+ *
+ * # Safety
+ *
+ * The buffer must be valid for writes of `key_length` bytes.
+ */
+themis_status_t themis_gen_sym_key(uint8_t *key, size_t *key_length);
+
+/**
+ * Returns the kind of an asymmetric key.
+ *
+ * Like in C Themis, only the key tag is inspected. Use `themis_is_valid_asym_key()`
+ * to validate the key data.
+ *
+ * # Safety
+ *
+ * The key must be valid for reads of `length` bytes.
+ */
+themis_key_kind_t themis_get_asym_key_kind(const uint8_t *key, size_t length);
+
+/**
+ * Validates an asymmetric key.
+ *
+ * Returns `THEMIS_SUCCESS` if the key is valid, `THEMIS_INVALID_PARAMETER` if this
+ * is not a key, or `THEMIS_DATA_CORRUPT` if the key data is corrupted.
+ *
+ * # Safety
+ *
+ * The key must be valid for reads of `length` bytes.
+ */
+themis_status_t themis_is_valid_asym_key(const uint8_t *key, size_t length);
+
+/**
+ * Returns version of the library.
+ *
+ * This is a static NUL-terminated string like "themis 0.1.0". Do not free it.
+ */
+const char *themis_version(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* THEMIS_THEMIS_H */
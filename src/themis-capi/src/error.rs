@@ -0,0 +1,60 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Status codes.
+
+use themis::Error;
+
+/// Status code returned by Themis functions.
+pub type themis_status_t = i32;
+
+/// Operation successful.
+pub const THEMIS_SUCCESS: themis_status_t = 0;
+/// General failure.
+pub const THEMIS_FAIL: themis_status_t = 11;
+/// Invalid parameter.
+pub const THEMIS_INVALID_PARAMETER: themis_status_t = 12;
+/// Memory allocation failure.
+pub const THEMIS_NO_MEMORY: themis_status_t = 13;
+/// Output buffer is too small.
+pub const THEMIS_BUFFER_TOO_SMALL: themis_status_t = 14;
+/// Input data is corrupted.
+pub const THEMIS_DATA_CORRUPT: themis_status_t = 15;
+/// Signature verification failed.
+pub const THEMIS_INVALID_SIGNATURE: themis_status_t = 16;
+/// Operation is not supported.
+pub const THEMIS_NOT_SUPPORTED: themis_status_t = 17;
+
+pub(crate) fn status_of(error: &Error) -> themis_status_t {
+    themis::status::status_code(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use themis::status;
+
+    #[test]
+    fn status_codes_match_themis() {
+        assert_eq!(THEMIS_SUCCESS, status::THEMIS_SUCCESS);
+        assert_eq!(THEMIS_FAIL, status::THEMIS_FAIL);
+        assert_eq!(THEMIS_INVALID_PARAMETER, status::THEMIS_INVALID_PARAMETER);
+        assert_eq!(THEMIS_NO_MEMORY, status::THEMIS_NO_MEMORY);
+        assert_eq!(THEMIS_BUFFER_TOO_SMALL, status::THEMIS_BUFFER_TOO_SMALL);
+        assert_eq!(THEMIS_DATA_CORRUPT, status::THEMIS_DATA_CORRUPT);
+        assert_eq!(THEMIS_INVALID_SIGNATURE, status::THEMIS_INVALID_SIGNATURE);
+        assert_eq!(THEMIS_NOT_SUPPORTED, status::THEMIS_NOT_SUPPORTED);
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key generation.
+
+use std::panic::{self, UnwindSafe};
+use std::ptr;
+
+use themis::keygen;
+
+use crate::error::*;
+
+/// Size of RSA keys generated by `themis_gen_rsa_key_pair()`, in bits.
+const RSA_KEY_BITS: usize = 2048;
+
+/// Size of symmetric keys generated by `themis_gen_sym_key()`, in bytes.
+const SYM_KEY_LENGTH: usize = 32;
+
+// Key sizes are fixed for given key parameters so the buffers can be checked
+// before doing the expensive key generation.
+const EC_PRIVATE_KEY_LENGTH: usize = 12 + 1 + 32;
+const EC_PUBLIC_KEY_LENGTH: usize = 12 + 1 + 32;
+const RSA_PRIVATE_KEY_LENGTH: usize = 12 + 4 + 256 + 5 * 128 + 256;
+const RSA_PUBLIC_KEY_LENGTH: usize = 12 + 4 + 256;
+
+/// Generates an ECDSA key pair.
+///
+/// If any of the buffers is `NULL` or too small, their lengths are updated with the sizes
+/// needed and `THEMIS_BUFFER_TOO_SMALL` is returned.
+///
+/// # Safety
+///
+/// Buffers must be valid for writes of the length indicated by length pointers.
+#[no_mangle]
+pub unsafe extern "C" fn themis_gen_ec_key_pair(
+    private_key: *mut u8,
+    private_key_length: *mut usize,
+    public_key: *mut u8,
+    public_key_length: *mut usize,
+) -> themis_status_t {
+    gen_key_pair(
+        (private_key, private_key_length, EC_PRIVATE_KEY_LENGTH),
+        (public_key, public_key_length, EC_PUBLIC_KEY_LENGTH),
+        || Ok(keygen::gen_ec_key_pair().split()),
+    )
+}
+
+/// Generates an RSA key pair.
+///
+/// Keys are 2048 bits long. If any of the buffers is `NULL` or too small, their lengths
+/// are updated with the sizes needed and `THEMIS_BUFFER_TOO_SMALL` is returned.
+///
+/// # Safety
+///
+/// Buffers must be valid for writes of the length indicated by length pointers.
+#[no_mangle]
+pub unsafe extern "C" fn themis_gen_rsa_key_pair(
+    private_key: *mut u8,
+    private_key_length: *mut usize,
+    public_key: *mut u8,
+    public_key_length: *mut usize,
+) -> themis_status_t {
+    gen_key_pair(
+        (private_key, private_key_length, RSA_PRIVATE_KEY_LENGTH),
+        (public_key, public_key_length, RSA_PUBLIC_KEY_LENGTH),
+        || Ok(keygen::gen_rsa_key_pair(RSA_KEY_BITS)?.split()),
+    )
+}
+
+/// Generates a symmetric key.
+///
+/// If `key` is `NULL` or `key_length` is zero, `key_length` is updated with the default
+/// key length and `THEMIS_BUFFER_TOO_SMALL` is returned. Otherwise the buffer is filled
+/// with `key_length` random bytes.
+///
+/// # Safety
+///
+/// The buffer must be valid for writes of `key_length` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn themis_gen_sym_key(
+    key: *mut u8,
+    key_length: *mut usize,
+) -> themis_status_t {
+    if key_length.is_null() {
+        return THEMIS_INVALID_PARAMETER;
+    }
+    if key.is_null() || *key_length == 0 {
+        *key_length = SYM_KEY_LENGTH;
+        return THEMIS_BUFFER_TOO_SMALL;
+    }
+    let key = std::slice::from_raw_parts_mut(key, *key_length);
+    match soter::rand::try_bytes(key) {
+        Ok(()) => THEMIS_SUCCESS,
+        Err(error) => status_of(&error.into()),
+    }
+}
+
+type KeyBuffer = (*mut u8, *mut usize, usize);
+
+unsafe fn gen_key_pair<F, Private, Public>(
+    private: KeyBuffer,
+    public: KeyBuffer,
+    generate: F,
+) -> themis_status_t
+where
+    F: FnOnce() -> themis::Result<(Private, Public)> + UnwindSafe,
+    Private: AsRef<[u8]>,
+    Public: AsRef<[u8]>,
+{
+    let (private_key, private_key_length, private_size) = private;
+    let (public_key, public_key_length, public_size) = public;
+    if private_key_length.is_null() || public_key_length.is_null() {
+        return THEMIS_INVALID_PARAMETER;
+    }
+    if private_key.is_null()
+        || public_key.is_null()
+        || *private_key_length < private_size
+        || *public_key_length < public_size
+    {
+        *private_key_length = private_size;
+        *public_key_length = public_size;
+        return THEMIS_BUFFER_TOO_SMALL;
+    }
+    let (private_bytes, public_bytes) = match panic::catch_unwind(generate) {
+        Ok(Ok(keys)) => keys,
+        Ok(Err(error)) => return status_of(&error),
+        Err(_) => return THEMIS_FAIL,
+    };
+    let private_bytes = private_bytes.as_ref();
+    let public_bytes = public_bytes.as_ref();
+    debug_assert_eq!(private_bytes.len(), private_size);
+    debug_assert_eq!(public_bytes.len(), public_size);
+    ptr::copy_nonoverlapping(private_bytes.as_ptr(), private_key, private_size);
+    ptr::copy_nonoverlapping(public_bytes.as_ptr(), public_key, public_size);
+    *private_key_length = private_size;
+    *public_key_length = public_size;
+    THEMIS_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use themis::keys::{get_key_kind, KeyKind};
+
+    #[test]
+    fn ec_key_pair() {
+        let mut private_key_length = 0;
+        let mut public_key_length = 0;
+        let status = unsafe {
+            themis_gen_ec_key_pair(
+                ptr::null_mut(),
+                &mut private_key_length,
+                ptr::null_mut(),
+                &mut public_key_length,
+            )
+        };
+        assert_eq!(status, THEMIS_BUFFER_TOO_SMALL);
+
+        let mut private_key = vec![0; private_key_length];
+        let mut public_key = vec![0; public_key_length];
+        let status = unsafe {
+            themis_gen_ec_key_pair(
+                private_key.as_mut_ptr(),
+                &mut private_key_length,
+                public_key.as_mut_ptr(),
+                &mut public_key_length,
+            )
+        };
+        assert_eq!(status, THEMIS_SUCCESS);
+        assert_eq!(get_key_kind(&private_key).unwrap(), KeyKind::EcdsaPrivate);
+        assert_eq!(get_key_kind(&public_key).unwrap(), KeyKind::EcdsaPublic);
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "backend-ring", not(feature = "rsa")), ignore)]
+    fn rsa_key_pair() {
+        let mut private_key = vec![0; RSA_PRIVATE_KEY_LENGTH];
+        let mut public_key = vec![0; RSA_PUBLIC_KEY_LENGTH];
+        let mut private_key_length = private_key.len();
+        let mut public_key_length = public_key.len() - 1;
+        let status = unsafe {
+            themis_gen_rsa_key_pair(
+                private_key.as_mut_ptr(),
+                &mut private_key_length,
+                public_key.as_mut_ptr(),
+                &mut public_key_length,
+            )
+        };
+        assert_eq!(status, THEMIS_BUFFER_TOO_SMALL);
+        assert_eq!(public_key_length, public_key.len());
+
+        let status = unsafe {
+            themis_gen_rsa_key_pair(
+                private_key.as_mut_ptr(),
+                &mut private_key_length,
+                public_key.as_mut_ptr(),
+                &mut public_key_length,
+            )
+        };
+        assert_eq!(status, THEMIS_SUCCESS);
+        assert_eq!(get_key_kind(&private_key).unwrap(), KeyKind::RsaPrivate);
+        assert_eq!(get_key_kind(&public_key).unwrap(), KeyKind::RsaPublic);
+    }
+
+    #[test]
+    fn sym_key() {
+        let mut key_length = 0;
+        let status = unsafe { themis_gen_sym_key(ptr::null_mut(), &mut key_length) };
+        assert_eq!(status, THEMIS_BUFFER_TOO_SMALL);
+        assert_eq!(key_length, SYM_KEY_LENGTH);
+
+        let mut key = vec![0; key_length];
+        let status = unsafe { themis_gen_sym_key(key.as_mut_ptr(), &mut key_length) };
+        assert_eq!(status, THEMIS_SUCCESS);
+        assert_ne!(key, vec![0; SYM_KEY_LENGTH]);
+    }
+
+    #[test]
+    fn null_lengths() {
+        let status = unsafe {
+            themis_gen_ec_key_pair(
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, THEMIS_INVALID_PARAMETER);
+        let status = unsafe { themis_gen_sym_key(ptr::null_mut(), ptr::null_mut()) };
+        assert_eq!(status, THEMIS_INVALID_PARAMETER);
+    }
+}
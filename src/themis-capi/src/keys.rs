@@ -0,0 +1,138 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key validation.
+
+use std::slice;
+
+use themis::keys::{get_key_kind, KeyKind};
+
+use crate::error::*;
+
+/// Kind of an asymmetric key.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum themis_key_kind_t {
+    /// Invalid key kind.
+    THEMIS_KEY_INVALID = 0,
+    /// RSA private key.
+    THEMIS_KEY_RSA_PRIVATE = 1,
+    /// RSA public key.
+    THEMIS_KEY_RSA_PUBLIC = 2,
+    /// ECDSA private key.
+    THEMIS_KEY_EC_PRIVATE = 3,
+    /// ECDSA public key.
+    THEMIS_KEY_EC_PUBLIC = 4,
+}
+
+/// Header of Soter containers.
+const CONTAINER_HEADER_LENGTH: usize = 12;
+
+/// Returns the kind of an asymmetric key.
+///
+/// Like in C Themis, only the key tag is inspected. Use `themis_is_valid_asym_key()`
+/// to validate the key data.
+///
+/// # Safety
+///
+/// The key must be valid for reads of `length` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn themis_get_asym_key_kind(
+    key: *const u8,
+    length: usize,
+) -> themis_key_kind_t {
+    if key.is_null() || length < CONTAINER_HEADER_LENGTH {
+        return themis_key_kind_t::THEMIS_KEY_INVALID;
+    }
+    match slice::from_raw_parts(key, 3) {
+        b"RRA" => themis_key_kind_t::THEMIS_KEY_RSA_PRIVATE,
+        b"URA" => themis_key_kind_t::THEMIS_KEY_RSA_PUBLIC,
+        b"REC" => themis_key_kind_t::THEMIS_KEY_EC_PRIVATE,
+        b"UEC" => themis_key_kind_t::THEMIS_KEY_EC_PUBLIC,
+        _ => themis_key_kind_t::THEMIS_KEY_INVALID,
+    }
+}
+
+/// Validates an asymmetric key.
+///
+/// Returns `THEMIS_SUCCESS` if the key is valid, `THEMIS_INVALID_PARAMETER` if this
+/// is not a key, or `THEMIS_DATA_CORRUPT` if the key data is corrupted.
+///
+/// # Safety
+///
+/// The key must be valid for reads of `length` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn themis_is_valid_asym_key(
+    key: *const u8,
+    length: usize,
+) -> themis_status_t {
+    let expected_kind = match themis_get_asym_key_kind(key, length) {
+        themis_key_kind_t::THEMIS_KEY_INVALID => return THEMIS_INVALID_PARAMETER,
+        themis_key_kind_t::THEMIS_KEY_RSA_PRIVATE => KeyKind::RsaPrivate,
+        themis_key_kind_t::THEMIS_KEY_RSA_PUBLIC => KeyKind::RsaPublic,
+        themis_key_kind_t::THEMIS_KEY_EC_PRIVATE => KeyKind::EcdsaPrivate,
+        themis_key_kind_t::THEMIS_KEY_EC_PUBLIC => KeyKind::EcdsaPublic,
+    };
+    match get_key_kind(slice::from_raw_parts(key, length)) {
+        Ok(kind) if kind == expected_kind => THEMIS_SUCCESS,
+        _ => THEMIS_DATA_CORRUPT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    // Keys generated by C Themis.
+    const EC_PRIVATE_KEY: &[u8] = b"\x52\x45\x43\x32\x00\x00\x00\x2d\x77\x3c\x25\x59\x00\xa3\x9c\xcd\x95\xd4\xa6\xf9\x21\xb0\x2f\xb1\xbf\x05\xa7\xbf\x70\x8b\x9a\x51\xb4\x5b\x3c\xbc\x64\x13\x27\x4f\xed\xb2\xc7\xf7\x60";
+    const EC_PUBLIC_KEY: &[u8] = b"\x55\x45\x43\x32\x00\x00\x00\x2d\x52\xb0\xbd\xf6\x03\x18\xe8\xb2\x77\xf6\x5d\x75\x30\x9b\xfe\x16\xc4\xf9\x59\x6a\xe4\x3e\xbf\xed\x1e\x38\xe8\xe9\xc0\xc3\x7f\x10\xef\x87\xba\x1e\x5d";
+
+    #[test]
+    fn key_kinds() {
+        unsafe {
+            assert_eq!(
+                themis_get_asym_key_kind(EC_PRIVATE_KEY.as_ptr(), EC_PRIVATE_KEY.len()),
+                themis_key_kind_t::THEMIS_KEY_EC_PRIVATE
+            );
+            assert_eq!(
+                themis_get_asym_key_kind(EC_PUBLIC_KEY.as_ptr(), EC_PUBLIC_KEY.len()),
+                themis_key_kind_t::THEMIS_KEY_EC_PUBLIC
+            );
+            assert_eq!(
+                themis_get_asym_key_kind(ptr::null(), 0),
+                themis_key_kind_t::THEMIS_KEY_INVALID
+            );
+        }
+    }
+
+    #[test]
+    fn key_validation() {
+        unsafe {
+            assert_eq!(
+                themis_is_valid_asym_key(EC_PRIVATE_KEY.as_ptr(), EC_PRIVATE_KEY.len()),
+                THEMIS_SUCCESS
+            );
+            assert_eq!(
+                themis_is_valid_asym_key(EC_PUBLIC_KEY.as_ptr(), EC_PUBLIC_KEY.len() - 1),
+                THEMIS_DATA_CORRUPT
+            );
+            assert_eq!(
+                themis_is_valid_asym_key(b"garbage garbage".as_ptr(), 15),
+                THEMIS_INVALID_PARAMETER
+            );
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C API of Themis.
+//!
+//! This crate exposes functions with the same names and signatures as C Themis
+//! (`libthemis`), implemented on top of themis.rs. Build it as a shared or static library
+//! and link it instead of C Themis.
+//!
+//! Only the parts of the API which themis.rs implements are available:
+//!
+//! - key generation: `themis_gen_ec_key_pair()`, `themis_gen_rsa_key_pair()`,
+//!   `themis_gen_sym_key()`
+//! - key validation: `themis_get_asym_key_kind()`, `themis_is_valid_asym_key()`
+//! - library version: `themis_version()`
+//!
+//! C header `themis/themis.h` is generated with cbindgen during the build and placed into
+//! `include` subdirectory of `OUT_DIR`. The same header is checked in as
+//! `include/themis/themis.h` in the crate directory, tests make sure it stays up to date.
+
+#![allow(non_camel_case_types)]
+
+mod error;
+mod keygen;
+mod keys;
+mod version;
+
+pub use error::*;
+pub use keygen::*;
+pub use keys::*;
+pub use version::*;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/include/themis/themis.h"));
+        let checked_in = include_str!("../include/themis/themis.h");
+        assert!(
+            generated == checked_in,
+            "include/themis/themis.h is outdated, copy it from OUT_DIR"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Library version.
+
+use std::os::raw::c_char;
+
+/// Version string returned by `themis_version()`, with terminating NUL.
+const THEMIS_VERSION_TEXT: &str = concat!("themis ", env!("CARGO_PKG_VERSION"), "\0");
+
+/// Returns version of the library.
+///
+/// This is a static NUL-terminated string like "themis 0.1.0". Do not free it.
+#[no_mangle]
+pub extern "C" fn themis_version() -> *const c_char {
+    THEMIS_VERSION_TEXT.as_ptr() as *const c_char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+
+    #[test]
+    fn version() {
+        let version = unsafe { CStr::from_ptr(themis_version()) };
+        let expected = format!("themis {}", themis::version());
+        assert_eq!(version.to_str(), Ok(expected.as_str()));
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates keys for Themis.
+//!
+//! Keys are written in Base64, one per file or line. Without file arguments the keys
+//! are printed to the standard output: private key first, then public key.
+//!
+//! ```text
+//! themis-keygen                       # ECDSA key pair to stdout
+//! themis-keygen key key.pub           # ECDSA key pair to files
+//! themis-keygen -t rsa -b 4096        # RSA key pair to stdout
+//! themis-keygen -t sym secret.key     # symmetric key to a file
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use clap::{App, Arg};
+use themis::keygen::{gen_ec_key_pair, gen_rsa_key_pair, gen_sym_key};
+
+fn main() {
+    let matches = App::new("themis-keygen")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Generates keys for Themis")
+        .arg(
+            Arg::with_name("type")
+                .short("t")
+                .long("type")
+                .takes_value(true)
+                .possible_values(&["ec", "rsa", "sym"])
+                .default_value("ec")
+                .help("Kind of keys to generate"),
+        )
+        .arg(
+            Arg::with_name("bits")
+                .short("b")
+                .long("bits")
+                .takes_value(true)
+                .possible_values(&["1024", "2048", "4096", "8192"])
+                .default_value("2048")
+                .help("RSA key size in bits"),
+        )
+        .arg(
+            Arg::with_name("private")
+                .value_name("PRIVATE_KEY")
+                .help("Output file for private or symmetric key"),
+        )
+        .arg(
+            Arg::with_name("public")
+                .value_name("PUBLIC_KEY")
+                .help("Output file for public key"),
+        )
+        .get_matches();
+
+    let private_path = matches.value_of("private").map(Path::new);
+    let public_path = matches.value_of("public").map(Path::new);
+
+    let key_type = matches.value_of("type").unwrap();
+    if key_type == "sym" && public_path.is_some() {
+        fail("symmetric keys do not have public keys");
+    }
+    if key_type != "sym" && private_path.is_some() != public_path.is_some() {
+        fail("specify output files for both private and public keys");
+    }
+
+    let result = match key_type {
+        "sym" => {
+            let key = gen_sym_key();
+            write_key(private_path, &key.to_base64(), true)
+        }
+        "rsa" => {
+            let bits = matches.value_of("bits").unwrap().parse().unwrap();
+            let key_pair = gen_rsa_key_pair(bits).unwrap_or_else(|e| fail(e));
+            write_key_pair(
+                private_path,
+                public_path,
+                &key_pair.private_key().to_base64(),
+                &key_pair.public_key().to_base64(),
+            )
+        }
+        _ => {
+            let key_pair = gen_ec_key_pair();
+            write_key_pair(
+                private_path,
+                public_path,
+                &key_pair.private_key().to_base64(),
+                &key_pair.public_key().to_base64(),
+            )
+        }
+    };
+    if let Err(e) = result {
+        fail(e);
+    }
+}
+
+fn write_key_pair(
+    private_path: Option<&Path>,
+    public_path: Option<&Path>,
+    private_key: &str,
+    public_key: &str,
+) -> io::Result<()> {
+    write_key(private_path, private_key, true)?;
+    write_key(public_path, public_key, false)
+}
+
+fn write_key(path: Option<&Path>, key: &str, secret: bool) -> io::Result<()> {
+    match path {
+        Some(path) => writeln!(create_file(path, secret)?, "{}", key),
+        None => writeln!(io::stdout(), "{}", key),
+    }
+}
+
+fn create_file(path: &Path, secret: bool) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // Do not let other users read secret keys.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(if secret { 0o600 } else { 0o644 });
+    }
+    #[cfg(not(unix))]
+    let _ = secret;
+    let file = options.open(path)?;
+    // The mode above applies only to new files. Existing files keep their permissions,
+    // so make sure that the secret key is not written into a world-readable file.
+    #[cfg(unix)]
+    {
+        use std::fs::Permissions;
+        use std::os::unix::fs::PermissionsExt;
+        if secret {
+            file.set_permissions(Permissions::from_mode(0o600))?;
+        }
+    }
+    Ok(file)
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("themis-keygen: {}", message);
+    process::exit(1);
+}
@@ -0,0 +1,71 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command-line interface tests.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn themis_keygen() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_themis-keygen"))
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("themis-keygen-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn key_pair_to_files() {
+    let private_path = temp_path("pair.key");
+    let public_path = temp_path("pair.pub");
+
+    let status = themis_keygen()
+        .arg(&private_path)
+        .arg(&public_path)
+        .status()
+        .expect("themis-keygen");
+    assert!(status.success());
+
+    let private_key = fs::read_to_string(&private_path).unwrap();
+    let public_key = fs::read_to_string(&public_path).unwrap();
+    let _ = fs::remove_file(&private_path);
+    let _ = fs::remove_file(&public_path);
+
+    assert!(private_key.starts_with("UkVDMg"));
+    assert!(public_key.starts_with("VUVDMg"));
+}
+
+#[test]
+#[cfg(unix)]
+fn secret_keys_are_not_readable_by_others() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = temp_path("existing.key");
+    fs::write(&path, "old contents").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let status = themis_keygen()
+        .args(["-t", "sym"])
+        .arg(&path)
+        .status()
+        .expect("themis-keygen");
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    let contents = fs::read_to_string(&path).unwrap();
+    let _ = fs::remove_file(&path);
+
+    assert!(status.success());
+    assert_eq!(mode & 0o777, 0o600);
+    assert_ne!(contents, "old contents");
+}
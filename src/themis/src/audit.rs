@@ -0,0 +1,131 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Security audit log.
+//!
+//! Applications can install an [`AuditSink`] to receive security-relevant events from Themis,
+//! for example, to forward them into a SIEM system. No sink is installed by default,
+//! in which case the events are not recorded anywhere.
+//!
+//! Events never contain secret data such as keys or their parts, only the description
+//! of what has happened.
+//!
+//! [`AuditSink`]: trait.AuditSink.html
+//!
+//! # Examples
+//!
+//! ```
+//! use themis::audit::{self, AuditEvent, AuditSink};
+//!
+//! struct LogSink;
+//!
+//! impl AuditSink for LogSink {
+//!     fn record(&self, event: &AuditEvent) {
+//!         eprintln!("themis: {:?}", event);
+//!     }
+//! }
+//!
+//! audit::set_sink(Box::new(LogSink));
+//! ```
+
+use std::sync::{Arc, RwLock};
+
+use crate::keys::KeyKind;
+
+/// Receiver of audit events.
+///
+/// Events are reported synchronously from the thread which performs the operation,
+/// so the sink should not block for a long time.
+pub trait AuditSink: Send + Sync {
+    /// Records an event.
+    fn record(&self, event: &AuditEvent);
+}
+
+/// Security-relevant event.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum AuditEvent {
+    /// Asymmetric key of given kind has been loaded.
+    ///
+    /// Reported when a key is parsed from bytes, Base64, or deserialized.
+    KeyLoaded {
+        /// Kind of the key.
+        kind: KeyKind,
+    },
+    /// Symmetric key has been loaded.
+    ///
+    /// Reported when a key is made from bytes, Base64, or deserialized.
+    SymmetricKeyLoaded,
+}
+
+static SINK: RwLock<Option<Arc<dyn AuditSink>>> = RwLock::new(None);
+
+/// Installs a sink for audit events, replacing the previous one.
+pub fn set_sink(sink: Box<dyn AuditSink>) {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::from(sink));
+}
+
+/// Removes the installed sink, if any.
+pub fn remove_sink() {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Reports an event to the installed sink.
+pub(crate) fn record(event: AuditEvent) {
+    // Do not hold the lock while the sink is running, it may want to replace itself.
+    let sink = SINK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(sink) = sink {
+        sink.record(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use hex_literal::hex;
+
+    use crate::keys::{EcdsaPublicKey, SymmetricKey};
+
+    struct TestSink(Arc<Mutex<Vec<AuditEvent>>>);
+
+    impl AuditSink for TestSink {
+        fn record(&self, event: &AuditEvent) {
+            self.0.lock().unwrap().push(*event);
+        }
+    }
+
+    const EC_PUBLIC_KEY: [u8; 45] = hex!("55454332 0000002d 52b0bdf6 0318e8b277f65d75309bfe16c4f9596ae43ebfed1e38e8e9c0c37f10ef87ba1e5d");
+
+    #[test]
+    fn key_loading() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        set_sink(Box::new(TestSink(events.clone())));
+
+        EcdsaPublicKey::try_from_slice(EC_PUBLIC_KEY).unwrap();
+        SymmetricKey::try_from_slice(b"secret").unwrap();
+        remove_sink();
+        SymmetricKey::try_from_slice(b"not recorded").unwrap();
+
+        // Other tests might load keys concurrently.
+        let events = events.lock().unwrap();
+        let ec_key_loaded = AuditEvent::KeyLoaded {
+            kind: KeyKind::EcdsaPublic,
+        };
+        assert!(events.contains(&ec_key_loaded));
+        assert!(events.contains(&AuditEvent::SymmetricKeyLoaded));
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{error, fmt, result};
+
+/// Result type for Themis operations.
+pub type Result<T> = result::Result<T, Error>;
+
+/// Error type for Themis operations.
+///
+/// The same advice as for [Soter errors][soter::Error] applies here: errors are a debugging
+/// aid. Abort the operation, do not trust the input, and do not try to be clever about
+/// recovering from particular error kinds.
+///
+/// Errors reported by Soter are available as the error [`source`].
+///
+/// [soter::Error]: ../soter/struct.Error.html
+/// [`source`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.source
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    source: Option<soter::Error>,
+}
+
+/// List of Themis error categories.
+///
+/// More error kinds may be added in the future, so make sure to handle unknown ones.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// General failure.
+    ///
+    /// Input data is malformed or corrupted, keys do not match, or something went wrong
+    /// in the cryptographic backend.
+    Failure,
+    /// Invalid parameter.
+    ///
+    /// The caller has violated API requirements, like providing an empty key.
+    /// This never indicates corrupted input data.
+    InvalidParameter,
+    /// Buffer is too small.
+    ///
+    /// Contains a suitable size for the buffer in bytes. Reallocate and try again.
+    BufferTooSmall(usize),
+    /// Operation is not supported.
+    NotSupported,
+    /// Authentication failed.
+    ///
+    /// Data has been tampered with or corrupted, or a wrong key or context is used.
+    ///
+    /// This is distinct from `MalformedData` only for diagnostics. Reject the data
+    /// either way, do not treat these errors differently otherwise.
+    AuthenticationFailed,
+    /// Malformed data.
+    ///
+    /// Input data cannot be parsed, it is truncated or has invalid headers.
+    MalformedData,
+    /// Keys do not match.
+    ///
+    /// The key has a wrong type or does not belong to the expected key pair.
+    KeyMismatch,
+    /// Invalid state.
+    ///
+    /// The operation is not allowed in the current state of the object or protocol.
+    ProtocolState,
+    /// Random number generator failed.
+    RandomFailure,
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|e| e as &(dyn error::Error + 'static))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Failure => write!(f, "failure"),
+            ErrorKind::InvalidParameter => write!(f, "invalid parameter"),
+            ErrorKind::BufferTooSmall(min) => write!(f, "buffer too small, need {} bytes", min),
+            ErrorKind::NotSupported => write!(f, "operation not supported"),
+            ErrorKind::AuthenticationFailed => write!(f, "authentication failed"),
+            ErrorKind::MalformedData => write!(f, "malformed data"),
+            ErrorKind::KeyMismatch => write!(f, "keys do not match"),
+            ErrorKind::ProtocolState => write!(f, "invalid state"),
+            ErrorKind::RandomFailure => write!(f, "random number generator failed"),
+        }
+    }
+}
+
+impl Error {
+    /// Constructs a new error of given kind.
+    pub(crate) fn new(kind: ErrorKind) -> Error {
+        Error { kind, source: None }
+    }
+
+    /// Returns the corresponding `ErrorKind` for this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl From<soter::Error> for Error {
+    fn from(other: soter::Error) -> Error {
+        let kind = match other.kind() {
+            soter::ErrorKind::Failure => ErrorKind::Failure,
+            soter::ErrorKind::InvalidParameter => ErrorKind::InvalidParameter,
+            soter::ErrorKind::BufferTooSmall(s) => ErrorKind::BufferTooSmall(s),
+            soter::ErrorKind::NotSupported => ErrorKind::NotSupported,
+            soter::ErrorKind::AuthenticationFailed => ErrorKind::AuthenticationFailed,
+            soter::ErrorKind::MalformedData => ErrorKind::MalformedData,
+            soter::ErrorKind::KeyMismatch => ErrorKind::KeyMismatch,
+            soter::ErrorKind::ProtocolState => ErrorKind::ProtocolState,
+            soter::ErrorKind::RandomFailure => ErrorKind::RandomFailure,
+            // Newer Soter versions may add more, treat them as general failures.
+            _ => ErrorKind::Failure,
+        };
+        Error {
+            kind,
+            source: Some(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::error::Error as _;
+
+    #[test]
+    fn soter_error_source() {
+        let err = Error::new(ErrorKind::Failure);
+        assert!(err.source().is_none());
+
+        let mut hash = soter::hash::Hash::new(soter::hash::Algorithm::SHA256);
+        let err = Error::from(hash.finalise(&mut [0; 16]).unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::BufferTooSmall(32));
+        let source = err.source().expect("Soter error");
+        assert!(source.is::<soter::Error>());
+    }
+}
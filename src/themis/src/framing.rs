@@ -0,0 +1,325 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message framing for stream transports.
+//!
+//! Themis objects produce and consume whole messages, but transports like TCP deliver
+//! a stream of bytes with no message boundaries. This module provides a simple framing
+//! scheme to carry messages over such transports: each message is preceded by its length
+//! as a 32-bit big-endian integer.
+//!
+//! ```text
+//! +----------------+------------------------+
+//! | length: u32 BE | payload: length bytes  |
+//! +----------------+------------------------+
+//! ```
+//!
+//! Use [`write_frame`] and [`read_frame`] with blocking I/O, or [`FrameDecoder`] if you
+//! receive data in arbitrary chunks from an event loop.
+//!
+//! Always limit the maximum frame size you are willing to accept. The length prefix comes
+//! from the peer, and without a limit a malicious peer can make you allocate gigabytes.
+//!
+//! [`write_frame`]: fn.write_frame.html
+//! [`read_frame`]: fn.read_frame.html
+//! [`FrameDecoder`]: struct.FrameDecoder.html
+
+use std::io::{self, Read, Write};
+
+/// Size of frame header in bytes.
+pub const HEADER_SIZE: usize = 4;
+
+/// Reasonable default for maximum frame size: 1 MiB.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+/// Writes a framed message.
+///
+/// The header and the payload are written with separate calls so you might want to use
+/// a buffered writer if that's a concern.
+///
+/// # Errors
+///
+/// Payloads longer than 4 GiB cannot be framed, an error of [`InvalidInput`] kind is
+/// returned for them. Other errors are passed through from the `writer`.
+///
+/// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use themis::framing;
+///
+/// let mut output = Vec::new();
+/// framing::write_frame(&mut output, b"message")?;
+///
+/// assert_eq!(output, b"\x00\x00\x00\x07message");
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> io::Result<()> {
+    writer.write_all(&encode_header(payload.len())?)?;
+    writer.write_all(payload)
+}
+
+/// Reads a framed message.
+///
+/// Returns `None` if the stream ends cleanly before the next frame starts.
+///
+/// # Errors
+///
+/// If the stream ends in the middle of a frame, an error of [`UnexpectedEof`] kind
+/// is returned.
+///
+/// If the frame is longer than `max_size` bytes, an error of [`InvalidData`] kind is
+/// returned without reading the payload. The stream is not usable after that since
+/// there is no way to find the next frame boundary.
+///
+/// Other errors are passed through from the `reader`.
+///
+/// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+/// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use themis::framing::{self, DEFAULT_MAX_FRAME_SIZE};
+///
+/// let mut input = &b"\x00\x00\x00\x07message"[..];
+///
+/// let frame = framing::read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE)?;
+/// assert_eq!(frame, Some(b"message".to_vec()));
+///
+/// let frame = framing::read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE)?;
+/// assert_eq!(frame, None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_frame<R: Read>(reader: &mut R, max_size: usize) -> io::Result<Option<Vec<u8>>> {
+    let mut header = [0; HEADER_SIZE];
+    let mut filled = 0;
+    while filled < header.len() {
+        match reader.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(truncated_frame()),
+            Ok(n) => filled += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    let length = decode_header(header, max_size)?;
+    let mut payload = vec![0; length];
+    reader.read_exact(&mut payload).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            truncated_frame()
+        } else {
+            e
+        }
+    })?;
+    Ok(Some(payload))
+}
+
+/// Incremental frame decoder.
+///
+/// Feed the data as it arrives with [`push`], then collect complete frames
+/// with [`next_frame`].
+///
+/// [`push`]: struct.FrameDecoder.html#method.push
+/// [`next_frame`]: struct.FrameDecoder.html#method.next_frame
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use themis::framing::{FrameDecoder, DEFAULT_MAX_FRAME_SIZE};
+///
+/// let mut decoder = FrameDecoder::new(DEFAULT_MAX_FRAME_SIZE);
+///
+/// decoder.push(b"\x00\x00\x00\x05hel");
+/// assert_eq!(decoder.next_frame()?, None);
+///
+/// decoder.push(b"lo\x00\x00");
+/// assert_eq!(decoder.next_frame()?, Some(b"hello".to_vec()));
+/// assert_eq!(decoder.next_frame()?, None);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    max_frame_size: usize,
+}
+
+impl FrameDecoder {
+    /// Makes a new decoder accepting frames up to `max_frame_size` bytes long.
+    ///
+    /// The limit is capped so that a frame with its header still fits into `usize`.
+    pub fn new(max_frame_size: usize) -> FrameDecoder {
+        FrameDecoder {
+            buffer: Vec::new(),
+            max_frame_size: max_frame_size.min(usize::MAX - HEADER_SIZE),
+        }
+    }
+
+    /// Appends received data to the decoder.
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Extracts the next complete frame, if there is one.
+    ///
+    /// Returns `None` if more data is needed to complete the frame.
+    ///
+    /// # Errors
+    ///
+    /// If the frame is longer than the maximum size, an error of [`InvalidData`] kind is
+    /// returned as soon as the frame header is received. The decoder keeps returning this
+    /// error afterwards since there is no way to find the next frame boundary.
+    ///
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    pub fn next_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if self.buffer.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+        let mut header = [0; HEADER_SIZE];
+        header.copy_from_slice(&self.buffer[..HEADER_SIZE]);
+        let length = decode_header(header, self.max_frame_size)?;
+        // Cannot overflow since the maximum frame size is capped in the constructor.
+        let frame_end = HEADER_SIZE + length;
+        if self.buffer.len() < frame_end {
+            return Ok(None);
+        }
+        let frame = self.buffer[HEADER_SIZE..frame_end].to_vec();
+        self.buffer.drain(..frame_end);
+        Ok(Some(frame))
+    }
+
+    /// Returns the number of buffered bytes which do not form a complete frame yet.
+    ///
+    /// If the stream ends while this is non-zero then the last frame has been truncated.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+fn encode_header(length: usize) -> io::Result<[u8; HEADER_SIZE]> {
+    if length > u32::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "payload too long to be framed",
+        ));
+    }
+    Ok((length as u32).to_be_bytes())
+}
+
+fn decode_header(header: [u8; HEADER_SIZE], max_size: usize) -> io::Result<usize> {
+    let length = u32::from_be_bytes(header) as usize;
+    if length > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame too long: {} bytes, limit {}", length, max_size),
+        ));
+    }
+    Ok(length)
+}
+
+fn truncated_frame() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "truncated frame")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let messages: &[&[u8]] = &[b"first", b"", b"third message"];
+        let mut stream = Vec::new();
+        for message in messages {
+            write_frame(&mut stream, message).unwrap();
+        }
+        let mut input = &stream[..];
+        for message in messages {
+            let frame = read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE).unwrap();
+            assert_eq!(frame.as_deref(), Some(*message));
+        }
+        assert_eq!(
+            read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn truncated_header() {
+        let mut input = &b"\x00\x00"[..];
+        let err = read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE).expect_err("truncated");
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn truncated_payload() {
+        let mut input = &b"\x00\x00\x00\x10short"[..];
+        let err = read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE).expect_err("truncated");
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn frame_too_long() {
+        let mut input = &b"\xFF\xFF\xFF\xFFwhatever"[..];
+        let err = read_frame(&mut input, DEFAULT_MAX_FRAME_SIZE).expect_err("too long");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn decoder_byte_by_byte() {
+        let mut stream = Vec::new();
+        write_frame(&mut stream, b"one").unwrap();
+        write_frame(&mut stream, b"two").unwrap();
+
+        let mut decoder = FrameDecoder::new(DEFAULT_MAX_FRAME_SIZE);
+        let mut frames = Vec::new();
+        for byte in &stream {
+            decoder.push(&[*byte]);
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                frames.push(frame);
+            }
+        }
+        assert_eq!(frames, vec![b"one".to_vec(), b"two".to_vec()]);
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+
+    #[test]
+    fn decoder_rejects_long_frames_early() {
+        let mut decoder = FrameDecoder::new(16);
+        decoder.push(b"\x00\x00\x01\x00");
+        let err = decoder.next_frame().expect_err("too long");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn decoder_caps_max_frame_size() {
+        let mut decoder = FrameDecoder::new(usize::MAX);
+        assert_eq!(decoder.max_frame_size, usize::MAX - HEADER_SIZE);
+        decoder.push(b"\xFF\xFF\xFF\xFFdata");
+        assert!(!matches!(decoder.next_frame(), Ok(Some(_))));
+    }
+
+    #[test]
+    fn decoder_reports_leftovers() {
+        let mut decoder = FrameDecoder::new(DEFAULT_MAX_FRAME_SIZE);
+        decoder.push(b"\x00\x00\x00\x08trunc");
+        assert_eq!(decoder.next_frame().unwrap(), None);
+        assert_eq!(decoder.buffered_len(), 9);
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Library version and build information.
+
+/// Data formats which this library can produce and consume.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum WireFormat {
+    /// Themis key containers, compatible with C Themis.
+    ///
+    /// See [`keys`](../keys/index.html) module.
+    KeyContainer,
+    /// Length-prefixed message framing.
+    ///
+    /// See [`framing`](../framing/index.html) module.
+    Framing,
+}
+
+const WIRE_FORMATS: &[WireFormat] = &[WireFormat::KeyContainer, WireFormat::Framing];
+
+const FEATURES: &[&str] = &[
+    #[cfg(feature = "backend-aws-lc")]
+    "backend-aws-lc",
+    #[cfg(feature = "backend-boringssl")]
+    "backend-boringssl",
+    #[cfg(feature = "backend-openssl")]
+    "backend-openssl",
+    #[cfg(feature = "backend-ring")]
+    "backend-ring",
+    #[cfg(feature = "fips")]
+    "fips",
+    #[cfg(feature = "rsa")]
+    "rsa",
+    #[cfg(feature = "serde")]
+    "serde",
+    #[cfg(feature = "tracing")]
+    "tracing",
+];
+
+/// Returns version of this library.
+///
+/// This is the version of `themis` crate, e.g., "0.1.0".
+///
+/// # Examples
+///
+/// ```
+/// println!("Using Themis {}", themis::version());
+/// ```
+pub fn version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Information about the library build.
+///
+/// Use [`build_info`] to obtain it.
+///
+/// [`build_info`]: fn.build_info.html
+#[derive(Debug, Clone)]
+pub struct BuildInfo {
+    backend: soter::BackendInfo,
+}
+
+/// Returns information about this build of the library.
+///
+/// # Examples
+///
+/// ```
+/// let info = themis::build_info();
+///
+/// println!("Themis {}", info.version());
+/// println!("Cryptographic backend: {}", info.backend().name());
+/// println!("Enabled features: {}", info.features().join(", "));
+/// ```
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        backend: soter::backend_info(),
+    }
+}
+
+impl BuildInfo {
+    /// Returns version of this library.
+    ///
+    /// This is the same as [`version`].
+    ///
+    /// [`version`]: fn.version.html
+    pub fn version(&self) -> &'static str {
+        version()
+    }
+
+    /// Returns information about the cryptographic backend.
+    pub fn backend(&self) -> &soter::BackendInfo {
+        &self.backend
+    }
+
+    /// Returns Cargo features which the library has been compiled with.
+    pub fn features(&self) -> &'static [&'static str] {
+        FEATURES
+    }
+
+    /// Returns data formats supported by this library.
+    pub fn wire_formats(&self) -> &'static [WireFormat] {
+        WIRE_FORMATS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_is_semver() {
+        let parts: Vec<&str> = version().split('.').collect();
+        assert_eq!(parts.len(), 3);
+        for part in parts {
+            assert!(
+                part.parse::<u32>().is_ok(),
+                "invalid version: {}",
+                version()
+            );
+        }
+    }
+
+    #[test]
+    fn features() {
+        let info = build_info();
+        assert_eq!(info.features().contains(&"rsa"), cfg!(feature = "rsa"));
+        assert_eq!(info.features().contains(&"serde"), cfg!(feature = "serde"));
+        assert_eq!(
+            info.features().contains(&"tracing"),
+            cfg!(feature = "tracing")
+        );
+        let backends = info
+            .features()
+            .iter()
+            .filter(|feature| feature.starts_with("backend-"))
+            .count();
+        assert_eq!(backends, 1);
+    }
+}
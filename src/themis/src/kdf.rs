@@ -0,0 +1,126 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hierarchical key derivation.
+//!
+//! Instead of storing a separate key for each tenant, table, or column, you can keep
+//! a single master key and derive subkeys for each context as needed:
+//!
+//! ```
+//! # fn main() -> themis::Result<()> {
+//! use themis::kdf::derive;
+//! use themis::keygen::gen_sym_key;
+//!
+//! let master_key = gen_sym_key();
+//!
+//! let email_key = derive(&master_key, &["tenant:42", "table:users", "column:email"])?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Derivation is deterministic: the same master key and path always produce the same
+//! subkey. Different paths produce unrelated keys. A subkey cannot be used to recover
+//! the master key or keys derived from it with other paths.
+//!
+//! Derivation is hierarchical: deriving with a path is the same as deriving with its
+//! first component, then with the rest. Therefore you can hand out an intermediate key
+//! (say, of a tenant) which is sufficient to derive all keys below it, but not others.
+//!
+//! Each step uses [Soter KDF] with the path component as context.
+//!
+//! [Soter KDF]: ../../soter/kdf/index.html
+
+use std::mem;
+
+use zeroize::Zeroizing;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::keys::SymmetricKey;
+
+/// Length of derived keys in bytes.
+pub const DERIVED_KEY_LENGTH: usize = 32;
+
+const LABEL: &str = "Themis hierarchical key derivation";
+
+/// Derives a subkey from the master key for the given path.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if the path is empty.
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+pub fn derive<P: AsRef<[u8]>>(master_key: &SymmetricKey, path: &[P]) -> Result<SymmetricKey> {
+    if path.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidParameter));
+    }
+    let mut current = Zeroizing::new(master_key.as_bytes().to_vec());
+    for component in path {
+        let mut next = Zeroizing::new(vec![0; DERIVED_KEY_LENGTH]);
+        soter::kdf::derive(Some(&current), LABEL, &[component.as_ref()], &mut next)?;
+        current = next;
+    }
+    SymmetricKey::from_vec(mem::take(&mut *current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(bytes: &[u8]) -> SymmetricKey {
+        SymmetricKey::try_from_slice(bytes).unwrap()
+    }
+
+    #[test]
+    fn deterministic() {
+        let master = key(b"master key");
+        let key_1 = derive(&master, &["tenant:42", "table:users"]).unwrap();
+        let key_2 = derive(&master, &["tenant:42", "table:users"]).unwrap();
+        assert_eq!(key_1.as_bytes(), key_2.as_bytes());
+        assert_eq!(key_1.as_bytes().len(), DERIVED_KEY_LENGTH);
+    }
+
+    #[test]
+    fn hierarchical() {
+        let master = key(b"master key");
+        let tenant = derive(&master, &["tenant:42"]).unwrap();
+        let direct = derive(&master, &["tenant:42", "table:users"]).unwrap();
+        let stepwise = derive(&tenant, &["table:users"]).unwrap();
+        assert_eq!(direct.as_bytes(), stepwise.as_bytes());
+    }
+
+    #[test]
+    fn different_paths() {
+        let master = key(b"master key");
+        let keys = [
+            derive(&master, &["tenant:42"]).unwrap(),
+            derive(&master, &["tenant:43"]).unwrap(),
+            derive(&master, &["tenant:4", "2"]).unwrap(),
+            derive(&master, &["tenant:42", ""]).unwrap(),
+            derive(&key(b"other key"), &["tenant:42"]).unwrap(),
+        ];
+        for (i, key_i) in keys.iter().enumerate() {
+            assert_ne!(key_i.as_bytes(), master.as_bytes());
+            for key_j in &keys[i + 1..] {
+                assert_ne!(key_i.as_bytes(), key_j.as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn empty_path() {
+        let empty: &[&str] = &[];
+        let err = derive(&key(b"master key"), empty).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+    }
+}
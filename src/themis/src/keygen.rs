@@ -0,0 +1,156 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key generation.
+//!
+//! Functions in this module generate new random keys suitable for use with Themis.
+//! Asymmetric keys are encoded in the same format as C Themis uses, so you can exchange
+//! them with other Themis implementations.
+//!
+//! With `tracing` feature enabled, key generation is reported in `debug` level spans.
+//! They include only the key parameters, never the key material.
+//!
+//! # Examples
+//!
+//! ```
+//! use themis::keygen::gen_ec_key_pair;
+//!
+//! let (private_key, public_key) = gen_ec_key_pair().split();
+//! ```
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::keys::{EcdsaKeyPair, RsaKeyPair, SymmetricKey};
+
+/// Default size of symmetric keys in bytes.
+const SYMMETRIC_KEY_SIZE: usize = 32;
+
+/// Generates a new ECDSA key pair.
+///
+/// Keys are generated on the NIST P-256 curve.
+///
+/// # Panics
+///
+/// This function panics if the cryptographic backend fails to generate keys.
+/// This usually indicates a problem with the system random number generator.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+pub fn gen_ec_key_pair() -> EcdsaKeyPair {
+    let key_pair = soter::ec::KeyPair::generate(soter::ec::Curve::P256)
+        .expect("failed to generate EC key pair");
+    EcdsaKeyPair::from_soter(&key_pair).expect("failed to encode EC key pair")
+}
+
+/// Generates a new RSA key pair.
+///
+/// Supported key sizes are 1024, 2048, 4096, and 8192 bits.
+/// Note that generation of large keys may take considerable time.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if the key size is not supported.
+///
+/// Returns an error of [`NotSupported`] kind if the cryptographic backend
+/// cannot generate RSA keys (e.g., ring), if Themis is built without `rsa` feature,
+/// or for 1024-bit keys in FIPS mode.
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+/// [`NotSupported`]: ../enum.ErrorKind.html#variant.NotSupported
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> themis::Result<()> {
+/// use themis::keygen::gen_rsa_key_pair;
+/// # if cfg!(feature = "backend-ring") || !cfg!(feature = "rsa") { return Ok(()); }
+///
+/// let key_pair = gen_rsa_key_pair(2048)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn gen_rsa_key_pair(bits: usize) -> Result<RsaKeyPair> {
+    match bits {
+        1024 | 2048 | 4096 | 8192 => {}
+        _ => return Err(Error::new(ErrorKind::InvalidParameter)),
+    }
+    let key_pair = soter::rsa::KeyPair::generate(bits)?;
+    RsaKeyPair::from_soter(&key_pair)
+}
+
+/// Generates a new symmetric key.
+///
+/// The key consists of 32 random bytes.
+///
+/// # Panics
+///
+/// This function panics if the system random number generator fails.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+pub fn gen_sym_key() -> SymmetricKey {
+    let mut bytes = vec![0; SYMMETRIC_KEY_SIZE];
+    soter::rand::bytes(&mut bytes);
+    SymmetricKey::from_vec(bytes).expect("symmetric key cannot be empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::keys::{get_key_kind, KeyKind};
+
+    #[test]
+    fn ec_key_pair() {
+        let (private_key, public_key) = gen_ec_key_pair().split();
+        assert_eq!(get_key_kind(&private_key).unwrap(), KeyKind::EcdsaPrivate);
+        assert_eq!(get_key_kind(&public_key).unwrap(), KeyKind::EcdsaPublic);
+        assert_eq!(&private_key.as_bytes()[0..4], b"REC2");
+        assert_eq!(&public_key.as_bytes()[0..4], b"UEC2");
+    }
+
+    #[test]
+    fn ec_keys_are_random() {
+        let key_pair_1 = gen_ec_key_pair();
+        let key_pair_2 = gen_ec_key_pair();
+        assert_ne!(key_pair_1.public_key(), key_pair_2.public_key());
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "backend-ring", not(feature = "rsa")), ignore)]
+    fn rsa_key_pair() {
+        let (private_key, public_key) = gen_rsa_key_pair(2048).unwrap().split();
+        assert_eq!(get_key_kind(&private_key).unwrap(), KeyKind::RsaPrivate);
+        assert_eq!(get_key_kind(&public_key).unwrap(), KeyKind::RsaPublic);
+        assert_eq!(&private_key.as_bytes()[0..4], b"RRA2");
+        assert_eq!(&public_key.as_bytes()[0..4], b"URA2");
+        // Public exponent and modulus are shared by both keys.
+        let public_data = &public_key.as_bytes()[12..];
+        let private_data = &private_key.as_bytes()[12..];
+        assert_eq!(&private_data[..4], &public_data[..4]);
+        assert_eq!(&private_data[private_data.len() - 256..], &public_data[4..]);
+    }
+
+    #[test]
+    fn rsa_invalid_sizes() {
+        for &bits in &[0, 512, 1000, 3072] {
+            let err = gen_rsa_key_pair(bits).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+        }
+    }
+
+    #[test]
+    fn symmetric_keys() {
+        let key_1 = gen_sym_key();
+        let key_2 = gen_sym_key();
+        assert_eq!(key_1.as_bytes().len(), SYMMETRIC_KEY_SIZE);
+        assert_ne!(key_1.as_bytes(), key_2.as_bytes());
+    }
+}
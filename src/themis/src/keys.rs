@@ -0,0 +1,775 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cryptographic keys.
+//!
+//! Themis APIs accept keys as typed objects instead of raw byte slices.
+//! Key data is structurally validated when the key object is constructed: the container
+//! checksum, the key tag, and the data length are checked. This way corrupted keys are
+//! detected early, and you cannot accidentally pass a public key where a private one
+//! is expected. Key values themselves (e.g., whether an EC point is on the curve)
+//! are not verified at this point.
+//!
+//! Asymmetric keys use the same binary format as C Themis: a [Soter container] with
+//! a tag identifying the key kind, followed by the key data. Symmetric keys are plain
+//! byte strings.
+//!
+//! Key data is wiped from memory when key objects are dropped. Private and symmetric keys
+//! are additionally kept in a [`SecureBuffer`] which is locked in memory, so that they
+//! are not swapped out to disk or included in core dumps.
+//!
+//! Like other Themis SDKs, keys can be exported and imported as standard Base64 strings
+//! (with padding). Base64 encoding of a key is the encoding of its binary form.
+//! Encoding and decoding are [constant-time] to avoid leaking private keys.
+//!
+//! With `serde` feature enabled, key types implement `Serialize` and `Deserialize`.
+//! Keys are serialized as Base64 strings for human-readable formats (like JSON or TOML)
+//! and as byte strings otherwise. Deserialized keys are validated the same way.
+//!
+//! [Soter container]: ../../soter/container/index.html
+//! [constant-time]: ../../soter/encoding/index.html
+//! [`SecureBuffer`]: ../../soter/memory/struct.SecureBuffer.html
+
+use std::fmt;
+
+use soter::container::Container;
+use soter::encoding;
+use soter::memory::{Redacted, SecureBuffer};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::audit::{self, AuditEvent};
+use crate::error::{Error, ErrorKind, Result};
+
+/// Kind of an asymmetric key.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum KeyKind {
+    /// RSA private key.
+    RsaPrivate,
+    /// RSA public key.
+    RsaPublic,
+    /// ECDSA private key.
+    EcdsaPrivate,
+    /// ECDSA public key.
+    EcdsaPublic,
+}
+
+/// Determines the kind of an asymmetric key.
+///
+/// The key data is structurally validated: the container checksum, the key tag,
+/// and the data length must be correct. Key values themselves are not verified,
+/// for example, whether an EC public key is a point on the curve, or whether
+/// a private key is in the valid range.
+///
+/// # Errors
+///
+/// Returns an error of [`MalformedData`] kind if the data is not a valid Themis key.
+///
+/// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+pub fn get_key_kind(key: impl AsRef<[u8]>) -> Result<KeyKind> {
+    let container = Container::parse(key.as_ref())?;
+    let tag = container.tag();
+    let (kind, expected_size) = match &tag[0..3] {
+        EC_PRIVATE_KEY_PREFIX => (KeyKind::EcdsaPrivate, ec_key_size(tag[3])?),
+        EC_PUBLIC_KEY_PREFIX => (KeyKind::EcdsaPublic, ec_key_size(tag[3])?),
+        RSA_PRIVATE_KEY_PREFIX => (KeyKind::RsaPrivate, rsa_private_key_size(tag[3])?),
+        RSA_PUBLIC_KEY_PREFIX => (KeyKind::RsaPublic, rsa_public_key_size(tag[3])?),
+        _ => return Err(Error::new(ErrorKind::MalformedData)),
+    };
+    if container.data().len() != expected_size {
+        return Err(Error::new(ErrorKind::MalformedData));
+    }
+    Ok(kind)
+}
+
+const EC_PRIVATE_KEY_PREFIX: &[u8] = b"REC";
+const EC_PUBLIC_KEY_PREFIX: &[u8] = b"UEC";
+const RSA_PRIVATE_KEY_PREFIX: &[u8] = b"RRA";
+const RSA_PUBLIC_KEY_PREFIX: &[u8] = b"URA";
+
+/// Returns the size of EC key data, given the key size tag.
+///
+/// Public keys are stored as compressed points. Private keys are stored as big-endian
+/// scalars, padded to the same length as public keys.
+fn ec_key_size(size_tag: u8) -> Result<usize> {
+    match size_tag {
+        b'2' => Ok(1 + 32),
+        b'3' => Ok(1 + 48),
+        b'5' => Ok(1 + 66),
+        _ => Err(Error::new(ErrorKind::MalformedData)),
+    }
+}
+
+/// Returns the size of RSA modulus in bytes, given the key size tag.
+fn rsa_modulus_size(size_tag: u8) -> Result<usize> {
+    match size_tag {
+        b'1' => Ok(1024 / 8),
+        b'2' => Ok(2048 / 8),
+        b'4' => Ok(4096 / 8),
+        b'8' => Ok(8192 / 8),
+        _ => Err(Error::new(ErrorKind::MalformedData)),
+    }
+}
+
+/// Returns the size of RSA public key data, given the key size tag.
+///
+/// The public key is stored as public exponent (u32) followed by the modulus.
+fn rsa_public_key_size(size_tag: u8) -> Result<usize> {
+    let modulus = rsa_modulus_size(size_tag)?;
+    Ok(4 + modulus)
+}
+
+/// Returns the size of RSA private key data, given the key size tag.
+///
+/// The private key is stored as public exponent (u32), private exponent,
+/// primes p and q, CRT exponents dp and dq, CRT coefficient qp, and the modulus.
+/// The private exponent is as long as the modulus, other components are half of that.
+fn rsa_private_key_size(size_tag: u8) -> Result<usize> {
+    let modulus = rsa_modulus_size(size_tag)?;
+    Ok(4 + modulus + 5 * (modulus / 2) + modulus)
+}
+
+/// Key bytes, wiped on drop.
+#[derive(Clone)]
+struct KeyBytes(Vec<u8>);
+
+impl KeyBytes {
+    fn from_slice(bytes: &[u8]) -> KeyBytes {
+        KeyBytes(bytes.to_vec())
+    }
+
+    fn from_vec(bytes: Vec<u8>) -> KeyBytes {
+        KeyBytes(bytes)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Drop for KeyBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Secret key bytes, kept in locked memory and wiped on drop.
+#[derive(Clone)]
+struct SecretBytes(SecureBuffer);
+
+impl SecretBytes {
+    fn from_slice(bytes: &[u8]) -> SecretBytes {
+        SecretBytes(SecureBuffer::from_slice(bytes))
+    }
+
+    fn from_vec(mut bytes: Vec<u8>) -> SecretBytes {
+        let secret = SecretBytes::from_slice(&bytes);
+        bytes.zeroize();
+        secret
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+fn decode_base64(encoded: &[u8]) -> Result<Vec<u8>> {
+    Ok(encoding::base64_decode(encoded)?)
+}
+
+macro_rules! key_type {
+    ($(#[$meta:meta])* $name:ident, $kind:expr, secret) => {
+        key_type!($(#[$meta])* $name, $kind, SecretBytes);
+
+        // Never print out the secret key data.
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&Redacted::new(self.as_bytes()))
+                    .finish()
+            }
+        }
+    };
+    ($(#[$meta:meta])* $name:ident, $kind:expr, public) => {
+        key_type!($(#[$meta])* $name, $kind, KeyBytes);
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&self.as_bytes())
+                    .finish()
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.as_bytes() == other.as_bytes()
+            }
+        }
+
+        impl Eq for $name {}
+    };
+    ($(#[$meta:meta])* $name:ident, $kind:expr, $storage:ident) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        pub struct $name {
+            bytes: $storage,
+        }
+
+        impl $name {
+            /// Audit event reported when a key is loaded.
+            pub(crate) const LOADED: AuditEvent = AuditEvent::KeyLoaded { kind: $kind };
+
+            /// Parses a key from a byte slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error of [`MalformedData`] kind if the data is not a valid Themis key,
+            /// or an error of [`InvalidParameter`] kind if this is a valid key of some other
+            /// kind.
+            ///
+            /// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+            /// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+            pub fn try_from_slice(bytes: impl AsRef<[u8]>) -> Result<Self> {
+                let bytes = bytes.as_ref();
+                if get_key_kind(bytes)? != $kind {
+                    return Err(Error::new(ErrorKind::InvalidParameter));
+                }
+                audit::record(Self::LOADED);
+                Ok(Self {
+                    bytes: $storage::from_slice(bytes),
+                })
+            }
+
+            /// Returns key data.
+            pub fn as_bytes(&self) -> &[u8] {
+                self.bytes.as_bytes()
+            }
+
+            /// Parses a key from a Base64 string.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error of [`MalformedData`] kind if the string is not valid Base64
+            /// or does not contain a valid Themis key, or an error of [`InvalidParameter`]
+            /// kind if this is a valid key of some other kind.
+            ///
+            /// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+            /// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+            pub fn from_base64(encoded: impl AsRef<[u8]>) -> Result<Self> {
+                let key = Self::from_vec(decode_base64(encoded.as_ref())?)?;
+                audit::record(Self::LOADED);
+                Ok(key)
+            }
+
+            /// Returns key data encoded in Base64.
+            ///
+            /// Note that the returned string is not wiped from memory when dropped.
+            pub fn to_base64(&self) -> String {
+                encoding::base64_encode(self.as_bytes())
+            }
+
+            /// Takes ownership of encoded key, validating it.
+            pub(crate) fn from_vec(bytes: Vec<u8>) -> Result<Self> {
+                // Wrap the data first so that it's wiped in case of errors.
+                let bytes = $storage::from_vec(bytes);
+                if get_key_kind(bytes.as_bytes())? != $kind {
+                    return Err(Error::new(ErrorKind::InvalidParameter));
+                }
+                Ok(Self { bytes })
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                self.as_bytes()
+            }
+        }
+    };
+}
+
+key_type!(
+    /// RSA private key.
+    RsaPrivateKey,
+    KeyKind::RsaPrivate,
+    secret
+);
+
+key_type!(
+    /// RSA public key.
+    RsaPublicKey,
+    KeyKind::RsaPublic,
+    public
+);
+
+key_type!(
+    /// ECDSA private key.
+    EcdsaPrivateKey,
+    KeyKind::EcdsaPrivate,
+    secret
+);
+
+key_type!(
+    /// ECDSA public key.
+    EcdsaPublicKey,
+    KeyKind::EcdsaPublic,
+    public
+);
+
+/// RSA key pair.
+#[derive(Debug, Clone)]
+pub struct RsaKeyPair {
+    private_key: RsaPrivateKey,
+    public_key: RsaPublicKey,
+}
+
+/// ECDSA key pair.
+#[derive(Debug, Clone)]
+pub struct EcdsaKeyPair {
+    private_key: EcdsaPrivateKey,
+    public_key: EcdsaPublicKey,
+}
+
+macro_rules! key_pair_type {
+    ($name:ident, $private:ident, $public:ident) => {
+        impl $name {
+            /// Returns the private key of this pair.
+            pub fn private_key(&self) -> &$private {
+                &self.private_key
+            }
+
+            /// Returns the public key of this pair.
+            pub fn public_key(&self) -> &$public {
+                &self.public_key
+            }
+
+            /// Splits this pair into private and public keys.
+            pub fn split(self) -> ($private, $public) {
+                (self.private_key, self.public_key)
+            }
+        }
+    };
+}
+
+key_pair_type!(RsaKeyPair, RsaPrivateKey, RsaPublicKey);
+key_pair_type!(EcdsaKeyPair, EcdsaPrivateKey, EcdsaPublicKey);
+
+impl EcdsaKeyPair {
+    /// Encodes raw EC keys in Themis format.
+    pub(crate) fn from_soter(key_pair: &soter::ec::KeyPair) -> Result<EcdsaKeyPair> {
+        let size_tag = match key_pair.curve() {
+            soter::ec::Curve::P256 => b'2',
+        };
+
+        // Private scalar is padded with a zero byte to the size of public key.
+        let mut private_data = Zeroizing::new(Vec::with_capacity(ec_key_size(size_tag)?));
+        private_data.push(0);
+        private_data.extend_from_slice(key_pair.private_key());
+
+        // Wrap the private key right away so that it's wiped in case of errors.
+        let private_key = encode_key(EC_PRIVATE_KEY_PREFIX, size_tag, &private_data)?;
+        let private_key = EcdsaPrivateKey::from_vec(private_key)?;
+        let public_key = encode_key(EC_PUBLIC_KEY_PREFIX, size_tag, key_pair.public_key())?;
+        let public_key = EcdsaPublicKey::from_vec(public_key)?;
+
+        Ok(EcdsaKeyPair {
+            private_key,
+            public_key,
+        })
+    }
+}
+
+impl RsaKeyPair {
+    /// Encodes raw RSA keys in Themis format.
+    pub(crate) fn from_soter(key_pair: &soter::rsa::KeyPair) -> Result<RsaKeyPair> {
+        let size_tag = match key_pair.modulus().len() * 8 {
+            1024 => b'1',
+            2048 => b'2',
+            4096 => b'4',
+            8192 => b'8',
+            _ => return Err(Error::new(ErrorKind::InvalidParameter)),
+        };
+        let public_exponent = key_pair.public_exponent().to_be_bytes();
+
+        let mut private_data = Zeroizing::new(Vec::with_capacity(rsa_private_key_size(size_tag)?));
+        private_data.extend_from_slice(&public_exponent);
+        private_data.extend_from_slice(key_pair.private_exponent());
+        private_data.extend_from_slice(key_pair.p());
+        private_data.extend_from_slice(key_pair.q());
+        private_data.extend_from_slice(key_pair.dp());
+        private_data.extend_from_slice(key_pair.dq());
+        private_data.extend_from_slice(key_pair.qp());
+        private_data.extend_from_slice(key_pair.modulus());
+
+        let mut public_data = Vec::with_capacity(rsa_public_key_size(size_tag)?);
+        public_data.extend_from_slice(&public_exponent);
+        public_data.extend_from_slice(key_pair.modulus());
+
+        // Wrap the private key right away so that it's wiped in case of errors.
+        let private_key = encode_key(RSA_PRIVATE_KEY_PREFIX, size_tag, &private_data)?;
+        let private_key = RsaPrivateKey::from_vec(private_key)?;
+        let public_key = encode_key(RSA_PUBLIC_KEY_PREFIX, size_tag, &public_data)?;
+        let public_key = RsaPublicKey::from_vec(public_key)?;
+
+        Ok(RsaKeyPair {
+            private_key,
+            public_key,
+        })
+    }
+}
+
+fn encode_key(prefix: &[u8], size_tag: u8, data: &[u8]) -> Result<Vec<u8>> {
+    let mut tag = [0; 4];
+    tag[0..3].copy_from_slice(prefix);
+    tag[3] = size_tag;
+    Ok(Container::new(tag, data).to_vec()?)
+}
+
+/// Symmetric encryption key.
+///
+/// Symmetric keys may contain arbitrary data, but they cannot be empty.
+#[derive(Clone)]
+pub struct SymmetricKey {
+    bytes: SecretBytes,
+}
+
+impl SymmetricKey {
+    /// Audit event reported when a key is loaded.
+    pub(crate) const LOADED: AuditEvent = AuditEvent::SymmetricKeyLoaded;
+
+    /// Makes a key from a byte slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`InvalidParameter`] kind if the slice is empty.
+    ///
+    /// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+    pub fn try_from_slice(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        let bytes = bytes.as_ref();
+        if bytes.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        audit::record(Self::LOADED);
+        Ok(SymmetricKey {
+            bytes: SecretBytes::from_slice(bytes),
+        })
+    }
+
+    /// Returns key data.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_bytes()
+    }
+
+    /// Makes a key from a Base64 string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`MalformedData`] kind if the string is not valid Base64,
+    /// or an error of [`InvalidParameter`] kind if the key is empty.
+    ///
+    /// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+    /// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+    pub fn from_base64(encoded: impl AsRef<[u8]>) -> Result<Self> {
+        let key = Self::from_vec(decode_base64(encoded.as_ref())?)?;
+        audit::record(Self::LOADED);
+        Ok(key)
+    }
+
+    /// Returns key data encoded in Base64.
+    ///
+    /// Note that the returned string is not wiped from memory when dropped.
+    pub fn to_base64(&self) -> String {
+        encoding::base64_encode(self.as_bytes())
+    }
+
+    /// Takes ownership of key data.
+    pub(crate) fn from_vec(bytes: Vec<u8>) -> Result<Self> {
+        let bytes = SecretBytes::from_vec(bytes);
+        if bytes.as_bytes().is_empty() {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        Ok(SymmetricKey { bytes })
+    }
+}
+
+impl AsRef<[u8]> for SymmetricKey {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for SymmetricKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SymmetricKey")
+            .field(&Redacted::new(self.as_bytes()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use std::marker::PhantomData;
+    use std::{fmt, mem, result};
+
+    use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use zeroize::Zeroizing;
+
+    use super::*;
+
+    /// Keys which can be parsed from Base64 and binary representations.
+    trait KeyData: Sized {
+        const EXPECTING: &'static str;
+
+        fn from_base64(encoded: &str) -> Result<Self>;
+        fn from_vec(bytes: Vec<u8>) -> Result<Self>;
+    }
+
+    struct KeyVisitor<K>(PhantomData<K>);
+
+    impl<'de, K: KeyData> Visitor<'de> for KeyVisitor<K> {
+        type Value = K;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(K::EXPECTING)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> result::Result<K, E> {
+            K::from_base64(value).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: de::Error>(self, value: &[u8]) -> result::Result<K, E> {
+            K::from_vec(value.to_vec()).map_err(E::custom)
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> result::Result<K, E> {
+            K::from_vec(value).map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> result::Result<K, A::Error> {
+            let mut bytes = Zeroizing::new(Vec::with_capacity(seq.size_hint().unwrap_or(0)));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            K::from_vec(mem::take(&mut *bytes)).map_err(de::Error::custom)
+        }
+    }
+
+    macro_rules! serde_key_type {
+        ($name:ident, $expecting:expr) => {
+            impl KeyData for $name {
+                const EXPECTING: &'static str = $expecting;
+
+                fn from_base64(encoded: &str) -> Result<Self> {
+                    $name::from_base64(encoded)
+                }
+
+                fn from_vec(bytes: Vec<u8>) -> Result<Self> {
+                    let key = $name::from_vec(bytes)?;
+                    audit::record($name::LOADED);
+                    Ok(key)
+                }
+            }
+
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> result::Result<S::Ok, S::Error> {
+                    if serializer.is_human_readable() {
+                        serializer.serialize_str(&self.to_base64())
+                    } else {
+                        serializer.serialize_bytes(self.as_bytes())
+                    }
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> result::Result<Self, D::Error> {
+                    let visitor = KeyVisitor::<$name>(PhantomData);
+                    if deserializer.is_human_readable() {
+                        deserializer.deserialize_str(visitor)
+                    } else {
+                        deserializer.deserialize_byte_buf(visitor)
+                    }
+                }
+            }
+        };
+    }
+
+    serde_key_type!(RsaPrivateKey, "RSA private key");
+    serde_key_type!(RsaPublicKey, "RSA public key");
+    serde_key_type!(EcdsaPrivateKey, "ECDSA private key");
+    serde_key_type!(EcdsaPublicKey, "ECDSA public key");
+    serde_key_type!(SymmetricKey, "symmetric key");
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EC_PUBLIC_KEY_BASE64: &str =
+            "VUVDMgAAAC1SsL32Axjosnf2XXUwm/4WxPlZauQ+v+0eOOjpwMN/EO+Huh5d";
+
+        #[test]
+        fn human_readable() {
+            let key = EcdsaPublicKey::from_base64(EC_PUBLIC_KEY_BASE64).unwrap();
+            let json = serde_json::to_string(&key).unwrap();
+            assert_eq!(json, format!("\"{}\"", EC_PUBLIC_KEY_BASE64));
+            let parsed: EcdsaPublicKey = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, key);
+        }
+
+        #[test]
+        fn binary() {
+            let key = EcdsaPublicKey::from_base64(EC_PUBLIC_KEY_BASE64).unwrap();
+            let encoded = bincode::serialize(&key).unwrap();
+            let parsed: EcdsaPublicKey = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(parsed, key);
+
+            let key = SymmetricKey::try_from_slice(b"secret").unwrap();
+            let encoded = bincode::serialize(&key).unwrap();
+            let parsed: SymmetricKey = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(parsed.as_bytes(), b"secret");
+        }
+
+        #[test]
+        fn validation() {
+            let json = format!("\"{}\"", EC_PUBLIC_KEY_BASE64);
+            assert!(serde_json::from_str::<EcdsaPrivateKey>(&json).is_err());
+            assert!(serde_json::from_str::<EcdsaPublicKey>("\"VUVDMgAAAC1S\"").is_err());
+            assert!(serde_json::from_str::<EcdsaPublicKey>("\"not base64\"").is_err());
+            assert!(serde_json::from_str::<SymmetricKey>("\"\"").is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hex_literal::hex;
+
+    use crate::status::{status_code, THEMIS_DATA_CORRUPT};
+
+    // Key pair generated by C Themis.
+    const EC_PRIVATE_KEY: [u8; 45] = hex!("52454332 0000002d 773c2559 00a39ccd95d4a6f921b02fb1bf05a7bf708b9a51b45b3cbc6413274fedb2c7f760");
+    const EC_PUBLIC_KEY: [u8; 45] = hex!("55454332 0000002d 52b0bdf6 0318e8b277f65d75309bfe16c4f9596ae43ebfed1e38e8e9c0c37f10ef87ba1e5d");
+
+    #[test]
+    fn key_kinds() {
+        assert_eq!(get_key_kind(EC_PRIVATE_KEY).unwrap(), KeyKind::EcdsaPrivate);
+        assert_eq!(get_key_kind(EC_PUBLIC_KEY).unwrap(), KeyKind::EcdsaPublic);
+    }
+
+    #[test]
+    fn parse_ec_keys() {
+        let private_key = EcdsaPrivateKey::try_from_slice(EC_PRIVATE_KEY).unwrap();
+        let public_key = EcdsaPublicKey::try_from_slice(EC_PUBLIC_KEY).unwrap();
+        assert_eq!(private_key.as_bytes(), &EC_PRIVATE_KEY[..]);
+        assert_eq!(public_key.as_bytes(), &EC_PUBLIC_KEY[..]);
+    }
+
+    #[test]
+    fn key_type_confusion() {
+        let err = EcdsaPrivateKey::try_from_slice(EC_PUBLIC_KEY).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+        let err = EcdsaPublicKey::try_from_slice(EC_PRIVATE_KEY).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+        let err = RsaPublicKey::try_from_slice(EC_PUBLIC_KEY).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+    }
+
+    #[test]
+    fn corrupted_keys() {
+        let mut corrupted = EC_PRIVATE_KEY;
+        corrupted[20] ^= 0xFF;
+        let err = EcdsaPrivateKey::try_from_slice(corrupted).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+        assert_eq!(status_code(&err), THEMIS_DATA_CORRUPT);
+
+        let err = EcdsaPublicKey::try_from_slice(&EC_PUBLIC_KEY[..44]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+
+        let err = EcdsaPublicKey::try_from_slice([]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+    }
+
+    #[test]
+    fn unknown_tags() {
+        // Valid containers, but not keys.
+        let not_a_key = Container::new(*b"UEC7", &EC_PUBLIC_KEY[12..])
+            .to_vec()
+            .unwrap();
+        let err = get_key_kind(not_a_key).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+        let not_a_key = Container::new(*b"SEC2", &EC_PUBLIC_KEY[12..])
+            .to_vec()
+            .unwrap();
+        let err = get_key_kind(not_a_key).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+    }
+
+    #[test]
+    fn symmetric_keys() {
+        let key = SymmetricKey::try_from_slice(b"secret").unwrap();
+        assert_eq!(key.as_bytes(), b"secret");
+
+        let err = SymmetricKey::try_from_slice([]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+    }
+
+    #[test]
+    fn base64_keys() {
+        // Keys exported by C Themis tools.
+        let private_key = EcdsaPrivateKey::from_base64(
+            "UkVDMgAAAC13PCVZAKOczZXUpvkhsC+xvwWnv3CLmlG0Wzy8ZBMnT+2yx/dg",
+        )
+        .unwrap();
+        let public_key = EcdsaPublicKey::from_base64(
+            "VUVDMgAAAC1SsL32Axjosnf2XXUwm/4WxPlZauQ+v+0eOOjpwMN/EO+Huh5d",
+        )
+        .unwrap();
+        assert_eq!(private_key.as_bytes(), &EC_PRIVATE_KEY[..]);
+        assert_eq!(public_key.as_bytes(), &EC_PUBLIC_KEY[..]);
+
+        let round_trip = EcdsaPublicKey::from_base64(public_key.to_base64()).unwrap();
+        assert_eq!(round_trip, public_key);
+
+        let key = SymmetricKey::from_base64("c2VjcmV0").unwrap();
+        assert_eq!(key.as_bytes(), b"secret");
+        assert_eq!(key.to_base64(), "c2VjcmV0");
+    }
+
+    #[test]
+    fn base64_errors() {
+        let err = EcdsaPublicKey::from_base64("not base64!").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+        let err = EcdsaPublicKey::from_base64(base64::encode(EC_PRIVATE_KEY)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+        let err = SymmetricKey::from_base64("").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+    }
+
+    #[test]
+    fn secrets_are_not_printed() {
+        let private_key = EcdsaPrivateKey::try_from_slice(EC_PRIVATE_KEY).unwrap();
+        assert_eq!(
+            format!("{:?}", private_key),
+            "EcdsaPrivateKey(<redacted 45 bytes>)"
+        );
+        let key = SymmetricKey::try_from_slice(b"secret").unwrap();
+        assert_eq!(format!("{:?}", key), "SymmetricKey(<redacted 6 bytes>)");
+    }
+}
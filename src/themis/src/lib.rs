@@ -1,3 +1,31 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod audit;
+pub mod framing;
+pub mod kdf;
+pub mod keygen;
+pub mod keys;
+pub mod shamir;
+pub mod status;
+
+mod error;
+mod info;
+
+pub use error::{Error, ErrorKind, Result};
+pub use info::{build_info, version, BuildInfo, WireFormat};
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -0,0 +1,227 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key backup with secret sharing.
+//!
+//! Secret keys can be split into _n_ shares so that any _k_ of them are enough to restore
+//! the key. Give the shares to different people or store them in different places,
+//! and no single share holder will be able to use or leak the key.
+//!
+//! ```
+//! # fn main() -> themis::Result<()> {
+//! use themis::keygen::gen_ec_key_pair;
+//! use themis::keys::EcdsaPrivateKey;
+//! use themis::shamir;
+//!
+//! let (private_key, _) = gen_ec_key_pair().split();
+//!
+//! // Any 3 of 5 shares are enough to restore the key.
+//! let shares = shamir::split(&private_key, 3, 5)?;
+//!
+//! let restored: EcdsaPrivateKey = shamir::combine(&shares[2..])?;
+//! assert_eq!(restored.as_bytes(), private_key.as_bytes());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Restored asymmetric keys are validated, so using too few or wrong shares is detected
+//! most of the time. Symmetric keys can contain arbitrary data, so in that case you will
+//! get some other key instead.
+//!
+//! This is [Shamir's secret sharing] implemented by Soter.
+//!
+//! [Shamir's secret sharing]: ../../soter/shamir/index.html
+
+use std::fmt;
+
+use soter::memory::Redacted;
+use soter::shamir::Share;
+
+use crate::error::Result;
+use crate::keys::{EcdsaPrivateKey, RsaPrivateKey, SymmetricKey};
+
+/// A share of a secret key.
+#[derive(Clone)]
+pub struct KeyShare {
+    share: Share,
+}
+
+impl KeyShare {
+    /// Parses a key share from a byte slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of [`MalformedData`] kind if the data is not a valid key share.
+    ///
+    /// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+    pub fn try_from_slice(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        Ok(KeyShare {
+            share: Share::try_from_slice(bytes)?,
+        })
+    }
+
+    /// Returns the index of this share, from 1 to 255.
+    pub fn index(&self) -> u8 {
+        self.share.index()
+    }
+
+    /// Returns share data.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.share.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for KeyShare {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// Never print out share data.
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("KeyShare")
+            .field(&self.index())
+            .field(&Redacted::new(&self.as_bytes()[1..]))
+            .finish()
+    }
+}
+
+/// Secret keys which can be split into shares.
+///
+/// This trait is sealed and cannot be implemented outside of Themis.
+pub trait ShareableKey: private::Sealed + Sized {}
+
+mod private {
+    use super::*;
+
+    pub trait Sealed {
+        fn secret_bytes(&self) -> &[u8];
+        fn from_secret_bytes(bytes: Vec<u8>) -> Result<Self>
+        where
+            Self: Sized;
+    }
+}
+
+macro_rules! shareable_key {
+    ($name:ident) => {
+        impl ShareableKey for $name {}
+
+        impl private::Sealed for $name {
+            fn secret_bytes(&self) -> &[u8] {
+                self.as_bytes()
+            }
+
+            fn from_secret_bytes(bytes: Vec<u8>) -> Result<Self> {
+                $name::from_vec(bytes)
+            }
+        }
+    };
+}
+
+shareable_key!(SymmetricKey);
+shareable_key!(EcdsaPrivateKey);
+shareable_key!(RsaPrivateKey);
+
+/// Splits a key into `count` shares, `threshold` of which are needed to restore it.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if `threshold` is less than 2,
+/// or if `count` is less than `threshold`.
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+pub fn split<K: ShareableKey>(key: &K, threshold: u8, count: u8) -> Result<Vec<KeyShare>> {
+    let shares = soter::shamir::split(key.secret_bytes(), threshold, count)?;
+    Ok(shares.into_iter().map(|share| KeyShare { share }).collect())
+}
+
+/// Restores a key from its shares.
+///
+/// # Errors
+///
+/// Returns an error of [`InvalidParameter`] kind if there are no shares, if shares
+/// have different lengths, or if there are duplicate shares.
+///
+/// If restored data is not a valid key of the requested kind, an error of [`MalformedData`]
+/// or [`InvalidParameter`] kind is returned, the same as when parsing keys. This is
+/// likely to happen if there are not enough shares.
+///
+/// [`InvalidParameter`]: ../enum.ErrorKind.html#variant.InvalidParameter
+/// [`MalformedData`]: ../enum.ErrorKind.html#variant.MalformedData
+pub fn combine<K: ShareableKey>(shares: &[KeyShare]) -> Result<K> {
+    // Shares are wiped on drop, these clones too.
+    let shares: Vec<Share> = shares.iter().map(|s| s.share.clone()).collect();
+    let bytes = soter::shamir::combine(&shares)?;
+    K::from_secret_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::ErrorKind;
+    use crate::keygen::{gen_ec_key_pair, gen_sym_key};
+    use crate::status::{status_code, THEMIS_DATA_CORRUPT};
+
+    #[test]
+    fn symmetric_keys() {
+        let key = gen_sym_key();
+        let shares = split(&key, 2, 3).unwrap();
+        assert_eq!(shares.len(), 3);
+        let restored: SymmetricKey = combine(&shares[..2]).unwrap();
+        assert_eq!(restored.as_bytes(), key.as_bytes());
+    }
+
+    #[test]
+    fn private_keys() {
+        let (key, _) = gen_ec_key_pair().split();
+        let shares = split(&key, 3, 3).unwrap();
+        let restored: EcdsaPrivateKey = combine(&shares).unwrap();
+        assert_eq!(restored.as_bytes(), key.as_bytes());
+    }
+
+    #[test]
+    fn not_enough_shares() {
+        let (key, _) = gen_ec_key_pair().split();
+        let shares = split(&key, 3, 5).unwrap();
+        let err = combine::<EcdsaPrivateKey>(&shares[..2]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedData);
+    }
+
+    #[test]
+    fn share_encoding() {
+        let key = gen_sym_key();
+        let shares = split(&key, 2, 2).unwrap();
+        let parsed: Vec<KeyShare> = shares
+            .iter()
+            .map(|share| KeyShare::try_from_slice(share).unwrap())
+            .collect();
+        let restored: SymmetricKey = combine(&parsed).unwrap();
+        assert_eq!(restored.as_bytes(), key.as_bytes());
+        assert_eq!(
+            format!("{:?}", parsed[1]),
+            "KeyShare(2, <redacted 32 bytes>)"
+        );
+    }
+
+    #[test]
+    fn malformed_shares() {
+        for bytes in [&[1][..], &[0, 1, 2]] {
+            let err = KeyShare::try_from_slice(bytes).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::MalformedData);
+            assert_eq!(status_code(&err), THEMIS_DATA_CORRUPT);
+        }
+    }
+}
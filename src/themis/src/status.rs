@@ -0,0 +1,141 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C Themis status codes.
+//!
+//! C Themis reports errors with numeric status codes. This module provides conversions
+//! between them and [`Error`] values, for interoperability with C code and for existing
+//! error handling and monitoring which expects these codes.
+//!
+//! Soter errors can be converted with `Error::from` first.
+//!
+//! Conversions are lossy: there are fewer status codes than error kinds, and status codes
+//! do not carry any additional information, such as the buffer size needed.
+//!
+//! [`Error`]: ../struct.Error.html
+//!
+//! # Examples
+//!
+//! ```
+//! use themis::keygen::gen_rsa_key_pair;
+//! use themis::status::{status_code, THEMIS_INVALID_PARAMETER};
+//!
+//! let error = gen_rsa_key_pair(1000).unwrap_err();
+//! assert_eq!(status_code(&error), THEMIS_INVALID_PARAMETER);
+//! ```
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Operation successful.
+pub const THEMIS_SUCCESS: i32 = 0;
+/// General failure.
+pub const THEMIS_FAIL: i32 = 11;
+/// Invalid parameter.
+pub const THEMIS_INVALID_PARAMETER: i32 = 12;
+/// Memory allocation failure.
+pub const THEMIS_NO_MEMORY: i32 = 13;
+/// Output buffer is too small.
+pub const THEMIS_BUFFER_TOO_SMALL: i32 = 14;
+/// Input data is corrupted.
+pub const THEMIS_DATA_CORRUPT: i32 = 15;
+/// Signature verification failed.
+pub const THEMIS_INVALID_SIGNATURE: i32 = 16;
+/// Operation is not supported.
+pub const THEMIS_NOT_SUPPORTED: i32 = 17;
+
+/// Returns C Themis status code corresponding to the error.
+///
+/// Error kinds without a dedicated status code are reported as `THEMIS_FAIL`,
+/// the same as C Themis does. In particular, this includes [`AuthenticationFailed`]
+/// since C Themis does not distinguish failed decryption from other failures.
+///
+/// [`AuthenticationFailed`]: ../enum.ErrorKind.html#variant.AuthenticationFailed
+pub fn status_code(error: &Error) -> i32 {
+    match error.kind() {
+        ErrorKind::Failure => THEMIS_FAIL,
+        ErrorKind::InvalidParameter => THEMIS_INVALID_PARAMETER,
+        ErrorKind::BufferTooSmall(_) => THEMIS_BUFFER_TOO_SMALL,
+        ErrorKind::NotSupported => THEMIS_NOT_SUPPORTED,
+        ErrorKind::MalformedData => THEMIS_DATA_CORRUPT,
+        ErrorKind::KeyMismatch => THEMIS_INVALID_PARAMETER,
+        ErrorKind::AuthenticationFailed => THEMIS_FAIL,
+        ErrorKind::ProtocolState => THEMIS_FAIL,
+        ErrorKind::RandomFailure => THEMIS_FAIL,
+    }
+}
+
+/// Converts C Themis status code into a result.
+///
+/// `THEMIS_SUCCESS` is converted into `Ok`, all other codes are errors.
+/// Unknown status codes are treated as [`Failure`].
+///
+/// Note that `THEMIS_BUFFER_TOO_SMALL` does not tell the size needed, so the resulting
+/// [`BufferTooSmall`] error always reports zero size.
+///
+/// [`Failure`]: ../enum.ErrorKind.html#variant.Failure
+/// [`BufferTooSmall`]: ../enum.ErrorKind.html#variant.BufferTooSmall
+pub fn from_status_code(status: i32) -> Result<()> {
+    let kind = match status {
+        THEMIS_SUCCESS => return Ok(()),
+        THEMIS_INVALID_PARAMETER => ErrorKind::InvalidParameter,
+        THEMIS_BUFFER_TOO_SMALL => ErrorKind::BufferTooSmall(0),
+        THEMIS_DATA_CORRUPT => ErrorKind::MalformedData,
+        THEMIS_INVALID_SIGNATURE => ErrorKind::AuthenticationFailed,
+        THEMIS_NOT_SUPPORTED => ErrorKind::NotSupported,
+        _ => ErrorKind::Failure,
+    };
+    Err(Error::new(kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn success() {
+        assert!(from_status_code(THEMIS_SUCCESS).is_ok());
+    }
+
+    #[test]
+    fn round_trip() {
+        for &status in &[
+            THEMIS_FAIL,
+            THEMIS_INVALID_PARAMETER,
+            THEMIS_BUFFER_TOO_SMALL,
+            THEMIS_DATA_CORRUPT,
+            THEMIS_NOT_SUPPORTED,
+        ] {
+            let error = from_status_code(status).unwrap_err();
+            assert_eq!(status_code(&error), status);
+        }
+    }
+
+    #[test]
+    fn lossy_codes() {
+        let error = from_status_code(THEMIS_NO_MEMORY).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Failure);
+        let error = from_status_code(THEMIS_INVALID_SIGNATURE).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AuthenticationFailed);
+        assert_eq!(status_code(&error), THEMIS_FAIL);
+        let error = from_status_code(42).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Failure);
+    }
+
+    #[test]
+    fn soter_errors() {
+        let mut hash = soter::hash::Hash::new(soter::hash::Algorithm::SHA256);
+        let error = Error::from(hash.finalise(&mut [0; 16]).unwrap_err());
+        assert_eq!(status_code(&error), THEMIS_BUFFER_TOO_SMALL);
+    }
+}
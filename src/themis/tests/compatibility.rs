@@ -0,0 +1,149 @@
+// Copyright 2020 themis.rs maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compatibility with C Themis.
+//!
+//! EC test vectors in this file have been produced by the reference C Themis implementation.
+//! RSA test vectors follow the key layout of C Themis (`soter_rsa_key.h`).
+//! They must keep working as is: any change here breaks interoperability.
+
+use themis::keys::{
+    get_key_kind, EcdsaPrivateKey, EcdsaPublicKey, KeyKind, RsaPrivateKey, RsaPublicKey,
+};
+
+// Key pair generated by `themis_gen_ec_key_pair()`.
+const EC_PRIVATE_KEY: &str = "UkVDMgAAAC13PCVZAKOczZXUpvkhsC+xvwWnv3CLmlG0Wzy8ZBMnT+2yx/dg";
+const EC_PUBLIC_KEY: &str = "VUVDMgAAAC1SsL32Axjosnf2XXUwm/4WxPlZauQ+v+0eOOjpwMN/EO+Huh5d";
+
+// Keys from two other, unrelated key pairs.
+const OTHER_EC_PRIVATE_KEY: &str = "UkVDMgAAAC1whm6SAJ7vIP18Kq5QXgLd413DMjnb6Z5jAeiRgUeekMqMC0+x";
+const OTHER_EC_PUBLIC_KEY: &str = "VUVDMgAAAC1FJv/DAmg8/L1Pl5l6ypyRqXUU9xQQaAgzfRZ+/gsjqgEdwXhc";
+
+// 1024-bit key pair generated with OpenSSL and laid out as C Themis stores RSA keys:
+// public exponent (u32), private exponent, p, q, dp, dq, qp, modulus -- all big-endian.
+// The containers have been assembled independently of this crate.
+const RSA_PRIVATE_KEY: &str = "UlJBMQAAAlDGIdt7AAEAARrzAOCztmka8hP4vcMjc1MOkQWZCVXZ22Q3cdzglA7R37LqTv6ZLhEw1gd+fiun+JGji5VAiNAlrYxtbt3HE+DcICuROmbWpQCndjBAZTi9QJkZ+2sfpwMSQBieA61SDTPGlXxsnT1NsypUg9wO5o5q52sbU+z81YP3Kaq5YUX50N2mrWDqrxv3M+xAgeVN497FPvsLSGcW8a+cEbIjUB/tNOggKhZZRLRWGFlGInGN57tOzN1SOcoVPIl+8wkQ68UQLCFigDUKneXFuV51/JggOm64IXtIKGt9DBQNVWgf8Hn0Ya5mfGJBy8owtn6JwpyYtF63E27XdODqsEXZS60rtAVZR+szQsXB/pAs3d8jbAyXtg3RU4jQf29HoJ+U92k55cw9IjoBjz5oj92F/X6021OMuE6pOuigpyCCGAaLSvupXxak5AnjJPAEZc4NTDFPKpwDmW5Oev/83ZBpRBKLJUx48qxqfdS9X3UDq8htdbqhPzz4Nv/iqEaExj81jQYmGT0MGoZ6EBlnUgAfae5aj9ikUP+wKhqkFRRoKVC7XzA4edXeYqlQkRcHNTUvXL0zwQEdR64ueQ+5Oux4Lzigx8MfLABkVY4b5CsQXV5fLE2/ZrmGSPjY5CcouScdPt1FeI1W9AOBGs4oy4ijRlCf3x9c9uOzbs5LP/RUUHxbioimxXzIe7I64ErkwLhZVyLfZFl0eViqMqvBqyBhJw0gxZDoV1l6z0/+x+gIq/L6mpp8RyBZF4yDoP/Mk0hHzw==";
+const RSA_PUBLIC_KEY: &str = "VVJBMQAAAJBp5O1yAAEAAaDHwx8sAGRVjhvkKxBdXl8sTb9muYZI+NjkJyi5Jx0+3UV4jVb0A4EazijLiKNGUJ/fH1z247Nuzks/9FRQfFuKiKbFfMh7sjrgSuTAuFlXIt9kWXR5WKoyq8GrIGEnDSDFkOhXWXrPT/7H6Air8vqamnxHIFkXjIOg/8yTSEfP";
+
+#[test]
+fn ec_key_kinds() {
+    let kind = |key| get_key_kind(base64::decode(key).unwrap()).unwrap();
+    assert_eq!(kind(EC_PRIVATE_KEY), KeyKind::EcdsaPrivate);
+    assert_eq!(kind(EC_PUBLIC_KEY), KeyKind::EcdsaPublic);
+    assert_eq!(kind(OTHER_EC_PRIVATE_KEY), KeyKind::EcdsaPrivate);
+    assert_eq!(kind(OTHER_EC_PUBLIC_KEY), KeyKind::EcdsaPublic);
+}
+
+#[test]
+fn ec_keys_round_trip() {
+    for &encoded in &[EC_PRIVATE_KEY, OTHER_EC_PRIVATE_KEY] {
+        let key = EcdsaPrivateKey::from_base64(encoded).unwrap();
+        assert_eq!(key.to_base64(), encoded);
+    }
+    for &encoded in &[EC_PUBLIC_KEY, OTHER_EC_PUBLIC_KEY] {
+        let key = EcdsaPublicKey::from_base64(encoded).unwrap();
+        assert_eq!(key.to_base64(), encoded);
+    }
+}
+
+#[test]
+fn ec_key_layout() {
+    let private_key = base64::decode(EC_PRIVATE_KEY).unwrap();
+    let public_key = base64::decode(EC_PUBLIC_KEY).unwrap();
+
+    let container = soter::container::Container::parse(&private_key).unwrap();
+    assert_eq!(container.tag(), b"REC2");
+    assert_eq!(container.data().len(), 33);
+    assert_eq!(container.data()[0], 0);
+
+    let container = soter::container::Container::parse(&public_key).unwrap();
+    assert_eq!(container.tag(), b"UEC2");
+    assert_eq!(container.data().len(), 33);
+    assert!(container.data()[0] == 0x02 || container.data()[0] == 0x03);
+}
+
+#[test]
+fn containers_are_reproducible() {
+    for &encoded in &[EC_PRIVATE_KEY, EC_PUBLIC_KEY] {
+        let bytes = base64::decode(encoded).unwrap();
+        let container = soter::container::Container::parse(&bytes).unwrap();
+        let reencoded = soter::container::Container::new(*container.tag(), container.data());
+        assert_eq!(reencoded.to_vec().unwrap(), bytes);
+    }
+}
+
+#[test]
+fn generated_keys_match_layout() {
+    let (private_key, public_key) = themis::keygen::gen_ec_key_pair().split();
+    assert_eq!(
+        private_key.as_bytes().len(),
+        base64::decode(EC_PRIVATE_KEY).unwrap().len()
+    );
+    assert_eq!(
+        public_key.as_bytes().len(),
+        base64::decode(EC_PUBLIC_KEY).unwrap().len()
+    );
+    assert_eq!(private_key.as_bytes()[12], 0);
+}
+
+#[test]
+fn rsa_key_kinds() {
+    let kind = |key| get_key_kind(base64::decode(key).unwrap()).unwrap();
+    assert_eq!(kind(RSA_PRIVATE_KEY), KeyKind::RsaPrivate);
+    assert_eq!(kind(RSA_PUBLIC_KEY), KeyKind::RsaPublic);
+}
+
+#[test]
+fn rsa_keys_round_trip() {
+    let key = RsaPrivateKey::from_base64(RSA_PRIVATE_KEY).unwrap();
+    assert_eq!(key.to_base64(), RSA_PRIVATE_KEY);
+    let key = RsaPublicKey::from_base64(RSA_PUBLIC_KEY).unwrap();
+    assert_eq!(key.to_base64(), RSA_PUBLIC_KEY);
+}
+
+#[test]
+fn rsa_key_layout() {
+    let private_key = base64::decode(RSA_PRIVATE_KEY).unwrap();
+    let public_key = base64::decode(RSA_PUBLIC_KEY).unwrap();
+
+    let private = soter::container::Container::parse(&private_key).unwrap();
+    assert_eq!(private.tag(), b"RRA1");
+    assert_eq!(private.data().len(), 4 + 128 + 5 * 64 + 128);
+
+    let public = soter::container::Container::parse(&public_key).unwrap();
+    assert_eq!(public.tag(), b"URA1");
+    assert_eq!(public.data().len(), 4 + 128);
+
+    // Public exponent 65537 and the modulus are shared by both keys.
+    let private_data = private.data();
+    let public_data = public.data();
+    assert_eq!(&public_data[..4], &[0, 1, 0, 1]);
+    assert_eq!(&private_data[..4], &public_data[..4]);
+    assert_eq!(&private_data[private_data.len() - 128..], &public_data[4..]);
+}
+
+#[test]
+#[cfg_attr(any(feature = "backend-ring", not(feature = "rsa")), ignore)]
+fn generated_rsa_keys_match_layout() {
+    let (private_key, public_key) = themis::keygen::gen_rsa_key_pair(1024).unwrap().split();
+    assert_eq!(
+        private_key.as_bytes().len(),
+        base64::decode(RSA_PRIVATE_KEY).unwrap().len()
+    );
+    assert_eq!(
+        public_key.as_bytes().len(),
+        base64::decode(RSA_PUBLIC_KEY).unwrap().len()
+    );
+    assert_eq!(&private_key.as_bytes()[0..4], b"RRA1");
+}